@@ -0,0 +1,560 @@
+//! `#[derive(DemoErrorLike)]` writes the `Display`, `Error` and `From` impls an
+//! error enum like `learn::result_demo::DemoError` would otherwise spell out by hand.
+//!
+//! ```
+//! use std::error::Error;
+//! use std::io;
+//!
+//! use learn_derive::DemoErrorLike;
+//!
+//! #[derive(Debug, DemoErrorLike)]
+//! enum LoadError {
+//!     // `#[from]` also makes the field the source
+//!     #[error("could not read the file")]
+//!     Io(#[from] io::Error),
+//!     // `{name}` and `{0}` are the variant's fields
+//!     #[error("line {line}: {message}")]
+//!     Syntax { line: usize, message: String },
+//!     // Extra arguments fill `{}`; `.0` and `.name` are fields there too
+//!     #[error("{} problems, first: {0:?}", .1.len())]
+//!     Many(&'static str, Vec<String>),
+//!     #[error("while {step}")]
+//!     Context { step: &'static str, #[source] source: Box<LoadError> },
+//!     // Display and source both come straight from the only field
+//!     #[error(transparent)]
+//!     Other(Box<dyn Error + Send + Sync>),
+//! }
+//!
+//! let err = LoadError::from(io::Error::from(io::ErrorKind::NotFound));
+//! assert_eq!(err.to_string(), "could not read the file");
+//! assert!(err.source().unwrap().is::<io::Error>());
+//!
+//! let err = LoadError::Context { step: "loading", source: Box::new(err) };
+//! assert_eq!(err.to_string(), "while loading");
+//! assert!(err.source().unwrap().is::<LoadError>());
+//!
+//! let err = LoadError::Syntax { line: 3, message: "no value".into() };
+//! assert_eq!(err.to_string(), "line 3: no value");
+//! assert!(err.source().is_none());
+//!
+//! let err = LoadError::Many("x", vec!["x".into(), "y".into()]);
+//! assert_eq!(err.to_string(), "2 problems, first: \"x\"");
+//!
+//! let err = LoadError::Other("x".parse::<u8>().unwrap_err().into());
+//! assert_eq!(err.to_string(), "invalid digit found in string");
+//! assert!(err.source().is_none());
+//! ```
+//!
+//! Misuse is a compile error. Every variant needs a message:
+//!
+//! ```compile_fail
+//! # use learn_derive::DemoErrorLike;
+//! #[derive(Debug, DemoErrorLike)]
+//! enum E {
+//!     Missing,
+//! }
+//! ```
+//!
+//! `#[from]` only works on a variant's only field, since `From` has nothing to fill the others with:
+//!
+//! ```compile_fail
+//! # use learn_derive::DemoErrorLike;
+//! #[derive(Debug, DemoErrorLike)]
+//! enum E {
+//!     #[error("at {1}")]
+//!     Io(#[from] std::io::Error, usize),
+//! }
+//! ```
+//!
+//! A variant has at most one source:
+//!
+//! ```compile_fail
+//! # use learn_derive::DemoErrorLike;
+//! #[derive(Debug, DemoErrorLike)]
+//! enum E {
+//!     #[error("two causes")]
+//!     Both { #[source] io: std::io::Error, #[source] parse: std::num::ParseIntError },
+//! }
+//! ```
+//!
+//! `transparent` needs exactly one field to forward to:
+//!
+//! ```compile_fail
+//! # use learn_derive::DemoErrorLike;
+//! #[derive(Debug, DemoErrorLike)]
+//! enum E {
+//!     #[error(transparent)]
+//!     Nothing,
+//! }
+//! ```
+//!
+//! The message must be a string literal:
+//!
+//! ```compile_fail
+//! # use learn_derive::DemoErrorLike;
+//! #[derive(Debug, DemoErrorLike)]
+//! enum E {
+//!     #[error(42)]
+//!     Answer,
+//! }
+//! ```
+//!
+//! Format arguments must name fields the variant has:
+//!
+//! ```compile_fail
+//! # use learn_derive::DemoErrorLike;
+//! #[derive(Debug, DemoErrorLike)]
+//! enum E {
+//!     #[error("column {column}")]
+//!     Syntax { row: usize },
+//! }
+//! ```
+//!
+//! Only enums are supported:
+//!
+//! ```compile_fail
+//! # use learn_derive::DemoErrorLike;
+//! #[derive(Debug, DemoErrorLike)]
+//! #[error("a struct")]
+//! struct E;
+//! ```
+
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+#[proc_macro_derive(DemoErrorLike, attributes(error, from, source))]
+pub fn derive_demo_error_like(input: TokenStream) -> TokenStream {
+    match parse_enum(input) {
+        Ok(item) => expand(&item).parse().expect("generated impls are valid Rust"),
+        Err(err) => err.into_compile_error(),
+    }
+}
+
+// A problem with the input, reported as a `compile_error!` at `span`
+struct Error {
+    span: Span,
+    message: String,
+}
+
+impl Error {
+    fn new(span: Span, message: impl Into<String>) -> Self {
+        Error { span, message: message.into() }
+    }
+
+    fn into_compile_error(self) -> TokenStream {
+        let mut message = Literal::string(&self.message);
+        message.set_span(self.span);
+        let mut args = Group::new(Delimiter::Parenthesis, TokenTree::from(message).into());
+        args.set_span(self.span);
+        let tokens: [TokenTree; 4] = [
+            Ident::new("compile_error", self.span).into(),
+            punct('!', self.span).into(),
+            args.into(),
+            punct(';', self.span).into(),
+        ];
+        tokens.into_iter().collect()
+    }
+}
+
+fn punct(c: char, span: Span) -> Punct {
+    let mut p = Punct::new(c, Spacing::Alone);
+    p.set_span(span);
+    p
+}
+
+struct Enum {
+    name: String,
+    variants: Vec<Variant>,
+}
+
+struct Variant {
+    name: String,
+    style: Style,
+    fields: Vec<Field>,
+    message: Message,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Unit,
+    Tuple,
+    Named,
+}
+
+struct Field {
+    // The field name, or `_0`, `_1`, ... for tuple fields; also the local it is bound to
+    binding: String,
+    ty: String,
+    from: bool,
+    source: bool,
+    // A `Box<T>`, whose source is the `T` so that it can be downcast
+    boxed: bool,
+}
+
+enum Message {
+    // `#[error(transparent)]`
+    Transparent,
+    // `#[error("...", args)]`; `args` starts with its comma when there are any
+    Format { literal: String, args: String },
+}
+
+fn parse_enum(input: TokenStream) -> Result<Enum, Error> {
+    let mut tokens = input.into_iter();
+    // Skipping attributes and visibility
+    let kind = tokens.by_ref().find_map(|token| match token {
+        TokenTree::Ident(ident) if ["enum", "struct", "union"].contains(&ident.to_string().as_str()) => Some(ident),
+        _ => None,
+    });
+    let kind = kind.ok_or_else(|| Error::new(Span::call_site(), "expected an enum"))?;
+    if kind.to_string() != "enum" {
+        return Err(Error::new(kind.span(), "DemoErrorLike can only be derived for enums"));
+    }
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name,
+        _ => return Err(Error::new(kind.span(), "expected the enum's name")),
+    };
+    let body = match tokens.next() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => g,
+        Some(TokenTree::Punct(p)) if p.as_char() == '<' => {
+            return Err(Error::new(p.span(), "DemoErrorLike does not support generic enums"));
+        }
+        _ => return Err(Error::new(name.span(), "expected the enum's variants")),
+    };
+    let variants = split_commas(body.stream(), false).into_iter().map(parse_variant).collect::<Result<_, _>>()?;
+    Ok(Enum { name: name.to_string(), variants })
+}
+
+fn parse_variant(tokens: Vec<TokenTree>) -> Result<Variant, Error> {
+    let mut tokens = tokens.into_iter().peekable();
+    let mut message = None;
+    for attr in attributes(&mut tokens) {
+        if attr.name != "error" {
+            continue;
+        }
+        if message.is_some() {
+            return Err(Error::new(attr.span, "duplicate #[error] attribute"));
+        }
+        message = Some(parse_message(attr)?);
+    }
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name,
+        other => return Err(Error::new(other.map_or(Span::call_site(), |t| t.span()), "expected a variant")),
+    };
+    let (style, fields) = match tokens.next() {
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => (Style::Tuple, parse_fields(g, Style::Tuple)?),
+        Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Brace => (Style::Named, parse_fields(g, Style::Named)?),
+        _ => (Style::Unit, Vec::new()),
+    };
+    let message = message.ok_or_else(|| {
+        Error::new(name.span(), format!("missing #[error(\"...\")] attribute on variant `{}`", name))
+    })?;
+    if matches!(message, Message::Transparent) && fields.len() != 1 {
+        return Err(Error::new(name.span(), "#[error(transparent)] needs a variant with exactly one field"));
+    }
+    if fields.iter().any(|f| f.from) && fields.len() != 1 {
+        return Err(Error::new(name.span(), "#[from] is only allowed on a variant's only field"));
+    }
+    if fields.iter().filter(|f| f.source || f.from).count() > 1 {
+        return Err(Error::new(name.span(), "a variant can have only one #[source] or #[from] field"));
+    }
+    Ok(Variant { name: name.to_string(), style, fields, message })
+}
+
+fn parse_fields(group: Group, style: Style) -> Result<Vec<Field>, Error> {
+    let mut fields = Vec::new();
+    for (index, tokens) in split_commas(group.stream(), true).into_iter().enumerate() {
+        let mut tokens = tokens.into_iter().peekable();
+        let (mut from, mut source) = (false, false);
+        for attr in attributes(&mut tokens) {
+            let flag = match attr.name.as_str() {
+                "from" => &mut from,
+                "source" => &mut source,
+                _ => continue,
+            };
+            if attr.args.is_some() {
+                return Err(Error::new(attr.span, format!("#[{}] takes no arguments", attr.name)));
+            }
+            *flag = true;
+        }
+        if matches!(tokens.peek(), Some(TokenTree::Ident(i)) if i.to_string() == "pub") {
+            tokens.next();
+            if matches!(tokens.peek(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis) {
+                tokens.next();
+            }
+        }
+        let binding = match style {
+            Style::Named => {
+                let name = tokens.next().map(|t| t.to_string()).unwrap_or_default();
+                tokens.next(); // the `:`
+                name
+            }
+            _ => format!("_{}", index),
+        };
+        let ty: Vec<TokenTree> = tokens.collect();
+        let path_end = ty.iter().position(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == '<'));
+        let boxed = path_end.is_some_and(|end| end > 0 && ty[end - 1].to_string() == "Box");
+        let ty = ty.into_iter().collect::<TokenStream>().to_string();
+        fields.push(Field { binding, ty, from, source, boxed });
+    }
+    Ok(fields)
+}
+
+fn parse_message(attr: Attribute) -> Result<Message, Error> {
+    let args = attr.args.ok_or_else(|| Error::new(attr.span, "expected #[error(\"...\")]"))?;
+    let mut tokens = args.stream().into_iter();
+    let first = tokens.next();
+    let literal = match first {
+        Some(TokenTree::Ident(ref i)) if i.to_string() == "transparent" => return Ok(Message::Transparent),
+        Some(TokenTree::Literal(ref l)) if l.to_string().starts_with(['"', 'r']) => l.to_string(),
+        _ => return Err(Error::new(args.span(), "expected a format string or `transparent`")),
+    };
+    let rest: TokenStream = tokens.collect();
+    match rest.clone().into_iter().next() {
+        None => {}
+        Some(TokenTree::Punct(p)) if p.as_char() == ',' => {}
+        Some(other) => return Err(Error::new(other.span(), "expected `,` after the format string")),
+    }
+    Ok(Message::Format { literal: numbered_fields(&literal), args: field_shorthand(rest).to_string() })
+}
+
+struct Attribute {
+    name: String,
+    args: Option<Group>,
+    span: Span,
+}
+
+// Consume the `#[...]` attributes at the front of `tokens`
+fn attributes(tokens: &mut std::iter::Peekable<impl Iterator<Item = TokenTree>>) -> Vec<Attribute> {
+    let mut attrs = Vec::new();
+    while matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == '#') {
+        tokens.next();
+        let Some(TokenTree::Group(body)) = tokens.next() else { break };
+        let mut inner = body.stream().into_iter();
+        let name = inner.next().map(|t| t.to_string()).unwrap_or_default();
+        let args = match inner.next() {
+            Some(TokenTree::Group(g)) => Some(g),
+            _ => None,
+        };
+        attrs.push(Attribute { name, args, span: body.span() });
+    }
+    attrs
+}
+
+// Split on top-level commas; with `angles`, commas inside `<...>` are not split on,
+// as in `HashMap<K, V>`
+fn split_commas(stream: TokenStream, angles: bool) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![Vec::new()];
+    let mut depth = 0usize;
+    let mut arrow = false;
+    for token in stream {
+        if let TokenTree::Punct(p) = &token {
+            match p.as_char() {
+                ',' if depth == 0 => {
+                    parts.push(Vec::new());
+                    continue;
+                }
+                '<' if angles => depth += 1,
+                // `->` in a function pointer type does not close anything
+                '>' if angles && !arrow => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            arrow = p.as_char() == '-' && p.spacing() == Spacing::Joint;
+        } else {
+            arrow = false;
+        }
+        parts.last_mut().expect("never empty").push(token);
+    }
+    parts.retain(|p| !p.is_empty());
+    parts
+}
+
+// `{0}` in a format string means tuple field 0, which is bound as `_0`
+fn numbered_fields(literal: &str) -> String {
+    let raw = literal.starts_with('r');
+    let mut out = String::with_capacity(literal.len());
+    let mut chars = literal.chars().peekable();
+    while let Some(c) = chars.next() {
+        out.push(c);
+        match c {
+            '\\' if !raw => {
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                    // `\u{...}` braces are not placeholders
+                    if escaped == 'u' {
+                        for c in chars.by_ref() {
+                            out.push(c);
+                            if c == '}' {
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+            '{' if chars.peek() == Some(&'{') => out.push(chars.next().expect("peeked")),
+            '{' => {
+                let mut name = String::new();
+                while let Some(&c) = chars.peek().filter(|&&c| c != '}' && c != ':') {
+                    name.push(c);
+                    chars.next();
+                }
+                if !name.is_empty() && name.bytes().all(|b| b.is_ascii_digit()) {
+                    out.push('_');
+                }
+                out.push_str(&name);
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+// In extra format arguments `.name` and `.0` stand for the variant's fields
+fn field_shorthand(stream: TokenStream) -> TokenStream {
+    let tokens: Vec<TokenTree> = stream.into_iter().collect();
+    let mut out: Vec<TokenTree> = Vec::with_capacity(tokens.len());
+    let mut i = 0;
+    while i < tokens.len() {
+        match &tokens[i] {
+            TokenTree::Punct(p) if p.as_char() == '.' && p.spacing() == Spacing::Alone && starts_operand(out.last()) => {
+                let field = match tokens.get(i + 1) {
+                    Some(TokenTree::Ident(name)) => Some(name.to_string()),
+                    Some(TokenTree::Literal(n)) if n.to_string().bytes().all(|b| b.is_ascii_digit()) => Some(format!("_{}", n)),
+                    _ => None,
+                };
+                if let Some(field) = field {
+                    out.push(Ident::new(&field, Span::call_site()).into());
+                    i += 2;
+                    continue;
+                }
+            }
+            TokenTree::Group(g) => {
+                let mut group = Group::new(g.delimiter(), field_shorthand(g.stream()));
+                group.set_span(g.span());
+                out.push(group.into());
+                i += 1;
+                continue;
+            }
+            _ => {}
+        }
+        out.push(tokens[i].clone());
+        i += 1;
+    }
+    out.into_iter().collect()
+}
+
+// Whether a `.` after `prev` starts an expression rather than accessing a member
+fn starts_operand(prev: Option<&TokenTree>) -> bool {
+    match prev {
+        None => true,
+        Some(TokenTree::Punct(p)) => !matches!(p.as_char(), '.' | '?'),
+        Some(_) => false,
+    }
+}
+
+fn expand(item: &Enum) -> String {
+    let name = &item.name;
+    let mut display = String::new();
+    let mut source = String::new();
+    let mut from = String::new();
+    let mut with_source = 0;
+
+    for v in &item.variants {
+        let bindings: Vec<&str> = v.fields.iter().map(|f| f.binding.as_str()).collect();
+        let arm = pattern(name, v, &bindings.join(", "));
+        match &v.message {
+            Message::Transparent => {
+                display += &format!("{} => ::std::fmt::Display::fmt({}, __formatter),\n", arm, bindings[0]);
+            }
+            Message::Format { literal, args } => {
+                display += &format!("{} => ::std::write!(__formatter, {}{}),\n", arm, literal, args);
+            }
+        }
+
+        let cause = match v.message {
+            Message::Transparent => Some((&v.fields[0], true)),
+            _ => v.fields.iter().find(|f| f.source || f.from).map(|f| (f, false)),
+        };
+        if let Some((field, transparent)) = cause {
+            with_source += 1;
+            let only = match v.style {
+                Style::Named => format!("{}, ..", field.binding),
+                _ => v.fields.iter().map(|f| if f.binding == field.binding { f.binding.as_str() } else { "_" }).collect::<Vec<_>>().join(", "),
+            };
+            let target = if field.boxed { format!("(**{})", field.binding) } else { field.binding.clone() };
+            let expr = if transparent {
+                format!("::std::error::Error::source({}.as_dyn_error())", target)
+            } else {
+                format!("::std::option::Option::Some({}.as_dyn_error())", target)
+            };
+            source += &format!("{} => {},\n", pattern(name, v, &only), expr);
+        }
+
+        if let Some(field) = v.fields.iter().find(|f| f.from) {
+            let construct = match v.style {
+                Style::Named => format!("{}::{} {{ {}: source }}", name, v.name, field.binding),
+                _ => format!("{}::{}(source)", name, v.name),
+            };
+            from += &format!(
+                "impl ::std::convert::From<{ty}> for {name} {{\n    fn from(source: {ty}) -> Self {{ {construct} }}\n}}\n",
+                ty = field.ty,
+            );
+        }
+    }
+
+    if item.variants.is_empty() {
+        display = "_ => match *self {},\n".to_string();
+    }
+    let error = if with_source == 0 {
+        format!("impl ::std::error::Error for {} {{}}\n", name)
+    } else {
+        if with_source < item.variants.len() {
+            source += "_ => ::std::option::Option::None,\n";
+        }
+        format!(
+            "impl ::std::error::Error for {name} {{
+    fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {{
+        {AS_DYN_ERROR}
+        match self {{
+{source}        }}
+    }}
+}}
+"
+        )
+    };
+    format!(
+        "impl ::std::fmt::Display for {name} {{
+    #[allow(unused_variables)]
+    fn fmt(&self, __formatter: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {{
+        match self {{
+{display}        }}
+    }}
+}}
+{error}{from}"
+    )
+}
+
+fn pattern(name: &str, v: &Variant, bindings: &str) -> String {
+    match v.style {
+        Style::Unit => format!("{}::{}", name, v.name),
+        Style::Tuple => format!("{}::{}({})", name, v.name, bindings),
+        Style::Named => format!("{}::{} {{ {} }}", name, v.name, bindings),
+    }
+}
+
+// Turns a source field into `&dyn Error`, whether it is a concrete error, a
+// `Box` of one, or a boxed trait object (which does not implement `Error`)
+const AS_DYN_ERROR: &str = "
+        trait AsDynError {
+            fn as_dyn_error(&self) -> &(dyn ::std::error::Error + 'static);
+        }
+        impl<T: ::std::error::Error + 'static> AsDynError for T {
+            fn as_dyn_error(&self) -> &(dyn ::std::error::Error + 'static) { self }
+        }
+        impl AsDynError for dyn ::std::error::Error + 'static {
+            fn as_dyn_error(&self) -> &(dyn ::std::error::Error + 'static) { self }
+        }
+        impl AsDynError for dyn ::std::error::Error + ::std::marker::Send + 'static {
+            fn as_dyn_error(&self) -> &(dyn ::std::error::Error + 'static) { self }
+        }
+        impl AsDynError for dyn ::std::error::Error + ::std::marker::Send + ::std::marker::Sync + 'static {
+            fn as_dyn_error(&self) -> &(dyn ::std::error::Error + 'static) { self }
+        }";
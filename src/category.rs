@@ -0,0 +1,71 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+
+use crate::chain;
+use crate::i18n::t;
+
+// Who has to act for an error to go away, and so whether retrying makes sense
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    // Bad input; the user has to fix what they typed or wrote
+    User,
+    // Missing files, permissions, broken disks; the machine has to be fixed
+    Environment,
+    // The input is well formed but breaks a business rule
+    Domain,
+    // An error we did not expect at all
+    Bug,
+    // Likely to succeed if simply tried again
+    Transient,
+}
+
+impl Category {
+    pub const ALL: [Category; 5] =
+        [Category::User, Category::Environment, Category::Domain, Category::Bug, Category::Transient];
+
+    pub fn is_transient(self) -> bool {
+        self == Category::Transient
+    }
+
+    // Stable identifier for machine-readable output
+    pub fn id(self) -> &'static str {
+        match self {
+            Category::User => "user",
+            Category::Environment => "environment",
+            Category::Domain => "domain",
+            Category::Bug => "bug",
+            Category::Transient => "transient",
+        }
+    }
+
+    // The category whose `id` is `id`
+    pub fn from_id(id: &str) -> Option<Category> {
+        Category::ALL.into_iter().find(|c| c.id() == id)
+    }
+
+    pub fn of_io(err: &io::Error) -> Category {
+        match err.kind() {
+            io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => Category::Transient,
+            _ => Category::Environment,
+        }
+    }
+
+    // For a type-erased error, see `chain::classify`; anything unrecognised is a `Bug`
+    pub fn of(err: &(dyn Error + 'static)) -> Category {
+        chain::classify(err).map_or(Category::Bug, |c| c.category)
+    }
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let key = match self {
+            Category::User => "category.user",
+            Category::Environment => "category.environment",
+            Category::Domain => "category.domain",
+            Category::Bug => "category.bug",
+            Category::Transient => "category.transient",
+        };
+        write!(f, "{}", t(key))
+    }
+}
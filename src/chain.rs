@@ -0,0 +1,103 @@
+use std::error::Error;
+use std::io;
+use std::net::AddrParseError;
+use std::num::{ParseFloatError, ParseIntError};
+use std::str::ParseBoolError;
+
+use crate::category::Category;
+use crate::codes;
+use crate::exit;
+use crate::i18n::t;
+use crate::json::Value;
+use crate::result_demo::DemoError;
+
+// `err` followed by every `Error::source()` below it, outermost first
+pub fn causes<'a>(err: &'a (dyn Error + 'static)) -> impl Iterator<Item = &'a (dyn Error + 'static)> {
+    std::iter::successors(Some(err), |&e| e.source())
+}
+
+// The first error of type `T` in the chain, however deeply it is wrapped
+pub fn find<'a, T: Error + 'static>(err: &'a (dyn Error + 'static)) -> Option<&'a T> {
+    causes(err).find_map(|e| e.downcast_ref::<T>())
+}
+
+// What the crate makes of an error, taken from the first error in its chain
+// whose type it recognises. An `io::Error` or std parse error counts as the
+// `DemoError::Io` or `DemoError::Parse` that `?` would have turned it into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Classification {
+    pub code: &'static str,
+    pub category: Category,
+    pub exit_code: u8,
+}
+
+pub fn classify(err: &(dyn Error + 'static)) -> Option<Classification> {
+    causes(err).find_map(|e| {
+        if let Some(e) = e.downcast_ref::<DemoError>() {
+            return Some(Classification { code: e.code(), category: e.category(), exit_code: exit::for_demo_error(e) });
+        }
+        if let Some(e) = e.downcast_ref::<io::Error>() {
+            return Some(Classification { code: codes::for_io(e), category: Category::of_io(e), exit_code: exit::for_io_error(e) });
+        }
+        let parse = e.is::<ParseIntError>() || e.is::<ParseFloatError>() || e.is::<ParseBoolError>() || e.is::<AddrParseError>();
+        parse.then(|| Classification { code: codes::for_parse(e), category: Category::User, exit_code: exit::DATA_ERR })
+    })
+}
+
+// `causes` for people: the source of an error whose message already includes
+// it, like the `io::Error` under `DemoError::Io`, is left out
+pub fn shown<'a>(err: &'a (dyn Error + 'static)) -> impl Iterator<Item = &'a (dyn Error + 'static)> {
+    std::iter::successors(Some(err), |&e| {
+        let source = e.source()?;
+        match e.downcast_ref::<DemoError>() {
+            Some(d) if d.embeds_source() => source.source(),
+            _ => Some(source),
+        }
+    })
+}
+
+// Errors grouped under `err` that are not its source, e.g. the members of `DemoError::Multiple`
+fn related<'a>(err: &'a (dyn Error + 'static)) -> &'a [DemoError] {
+    err.downcast_ref::<DemoError>().map_or(&[], DemoError::related)
+}
+
+// One line per error in the chain, each cause indented one level deeper;
+// grouped errors are listed as bullets under the error that holds them:
+//
+//   opening numbers file `numbers.txt`
+//     caused by: IO error: No such file or directory (os error 2)
+pub fn plain(err: &(dyn Error + 'static)) -> String {
+    let mut lines = Vec::new();
+    push_lines(&mut lines, err, 0, false);
+    lines.join("\n")
+}
+
+fn push_lines(lines: &mut Vec<String>, err: &(dyn Error + 'static), indent: usize, bullet: bool) {
+    for (depth, e) in shown(err).enumerate() {
+        let pad = "  ".repeat(indent + depth);
+        lines.push(match depth {
+            0 if bullet => format!("{}- {}", pad, e),
+            0 => format!("{}{}", pad, e),
+            _ => format!("{}{}: {}", pad, t("chain.caused_by"), e),
+        });
+        for child in related(e) {
+            push_lines(lines, child, indent + depth + 1, true);
+        }
+    }
+}
+
+// The same chain as a JSON object: `{"error": "...", "causes": ["...", ...]}`,
+// plus `"errors": [...]` holding one such object per grouped error
+pub fn json(err: &(dyn Error + 'static)) -> String {
+    json_value(err).to_string()
+}
+
+pub fn json_value(err: &(dyn Error + 'static)) -> Value {
+    let messages: Vec<String> = shown(err).skip(1).map(|e| e.to_string()).collect();
+    let grouped: Vec<Value> = shown(err).flat_map(related).map(|e| json_value(e)).collect();
+    let mut fields = vec![("error", Value::from(err.to_string())), ("causes", Value::from(messages))];
+    if !grouped.is_empty() {
+        fields.push(("errors", Value::Array(grouped)));
+    }
+    Value::object(fields)
+}
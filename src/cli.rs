@@ -0,0 +1,639 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::category::Category;
+use crate::chain;
+use crate::codes;
+use crate::context::ResultExt;
+use crate::demo::{self, Context, Demo, Registry};
+use crate::exit::{self, Outcome};
+use crate::fault::{FaultyFs, Injection, Plan};
+use crate::fs::RealFs;
+use crate::i18n::{self, t, tf, Locale};
+use crate::isolate;
+use crate::json::Value;
+use crate::numbers::{self, ErrorMode, NumberReader};
+use crate::panic_demo;
+use crate::result_demo::{read_number_from_file, DemoError};
+use crate::rules::RuleSet;
+use crate::snapshot::{self, Status};
+
+// Parsed command line
+#[derive(Debug, PartialEq)]
+pub struct Cli {
+    pub command: Command,
+    // `--lang`; `None` keeps the locale taken from the environment
+    pub lang: Option<Locale>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    List { filter: Option<String> },
+    // `isolate` runs every demo in a child process of its own
+    Run { names: Vec<String>, all: bool, filter: Option<String>, output: Output, faults: Plan, isolate: bool },
+    Numbers { path: PathBuf, keep_going: bool },
+    Rules { rules: PathBuf, numbers: PathBuf },
+    I18nCheck,
+    // Compare demo output with the checked-in snapshots, or rewrite them
+    Snapshot { update: bool },
+    // Long-form help for an error code; `None` lists every code
+    Explain { code: Option<String> },
+    Help,
+}
+
+// How `run` reports what the demos did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Output {
+    // Section headers and the demos' own output, for people
+    #[default]
+    Text,
+    // One JSON object per demo per line (JSON Lines), for tools
+    Json,
+}
+
+impl Output {
+    pub fn parse(s: &str) -> Option<Output> {
+        match s {
+            "text" => Some(Output::Text),
+            "json" => Some(Output::Json),
+            _ => None,
+        }
+    }
+}
+
+// A command line that could not be understood
+#[derive(Debug)]
+pub enum UsageError {
+    UnknownCommand(String),
+    UnknownFlag(String),
+    UnexpectedArgument(String),
+    MissingValue(&'static str),
+    MissingArgument(&'static str),
+    UnknownLocale(String),
+    UnknownOutput(String),
+    UnknownFault(String),
+    InvalidSeed(String),
+    UnknownCode(String),
+    NothingToRun,
+    // `run` was given demo names and `--all`, which would ignore the names
+    NamesWithAll,
+    // `--filter` left no demo to run or list
+    NoMatch(String),
+    // A flag such as `--inject` given to a command other than `run`
+    RunOnly(&'static str),
+    UnknownDemo { name: String, suggestions: Vec<&'static str> },
+}
+
+impl Display for UsageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsageError::UnknownCommand(c) => write!(f, "{}", tf("cli.unknown_command", &[("name", c)])),
+            UsageError::UnknownFlag(flag) => write!(f, "{}", tf("cli.unknown_flag", &[("name", flag)])),
+            UsageError::UnexpectedArgument(arg) => write!(f, "{}", tf("cli.unexpected_argument", &[("name", arg)])),
+            UsageError::MissingValue(flag) => write!(f, "{}", tf("cli.missing_value", &[("name", flag)])),
+            UsageError::MissingArgument(name) => write!(f, "{}", tf("cli.missing_argument", &[("name", name)])),
+            UsageError::UnknownLocale(lang) => write!(f, "{}", tf("cli.unknown_locale", &[("name", lang)])),
+            UsageError::UnknownOutput(format) => write!(f, "{}", tf("cli.unknown_output", &[("name", format)])),
+            UsageError::UnknownFault(spec) => write!(f, "{}", tf("cli.unknown_fault", &[("name", spec)])),
+            UsageError::InvalidSeed(seed) => write!(f, "{}", tf("cli.invalid_seed", &[("name", seed)])),
+            UsageError::UnknownCode(code) => write!(f, "{}", tf("cli.unknown_code", &[("name", code)])),
+            UsageError::NothingToRun => write!(f, "{}", t("cli.nothing_to_run")),
+            UsageError::NamesWithAll => write!(f, "{}", t("cli.names_with_all")),
+            UsageError::NoMatch(filter) => write!(f, "{}", tf("cli.no_match", &[("name", filter)])),
+            UsageError::RunOnly(flag) => write!(f, "{}", tf("cli.run_only", &[("name", flag)])),
+            UsageError::UnknownDemo { name, suggestions } => {
+                write!(f, "{}", tf("cli.unknown_demo", &[("name", name)]))?;
+                if !suggestions.is_empty() {
+                    write!(f, "{}", tf("cli.did_you_mean", &[("names", &suggestions.join(", "))]))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Cli, UsageError> {
+    let mut args = args.into_iter();
+    let mut positional = Vec::new();
+    let mut all = false;
+    let mut filter = None;
+    let mut lang = None;
+    let mut help = false;
+    let mut keep_going = false;
+    let mut update_snapshots = false;
+    let mut isolate = false;
+    let mut output = Output::default();
+    let mut faults = Plan::default();
+    // The last flag seen that only `run` understands
+    let mut run_only = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--filter" => filter = Some(args.next().ok_or(UsageError::MissingValue("--filter"))?),
+            "--lang" => {
+                let value = args.next().ok_or(UsageError::MissingValue("--lang"))?;
+                lang = Some(Locale::parse(&value).ok_or(UsageError::UnknownLocale(value))?);
+            }
+            "--output" => {
+                let value = args.next().ok_or(UsageError::MissingValue("--output"))?;
+                output = Output::parse(&value).ok_or(UsageError::UnknownOutput(value))?;
+            }
+            "--inject" => {
+                run_only = Some("--inject");
+                let value = args.next().ok_or(UsageError::MissingValue("--inject"))?;
+                faults.injections.push(Injection::parse(&value).ok_or(UsageError::UnknownFault(value))?);
+            }
+            "--chaos" => {
+                run_only = Some("--chaos");
+                let value = args.next().ok_or(UsageError::MissingValue("--chaos"))?;
+                faults.chaos = Some(value.parse().map_err(|_| UsageError::InvalidSeed(value))?);
+            }
+            "--keep-going" => keep_going = true,
+            "--update-snapshots" => update_snapshots = true,
+            "--isolate" => {
+                run_only = Some("--isolate");
+                isolate = true;
+            }
+            "--help" | "-h" => help = true,
+            flag if flag.starts_with("--") => return Err(UsageError::UnknownFlag(arg)),
+            _ => positional.push(arg),
+        }
+    }
+
+    if help {
+        return Ok(Cli { command: Command::Help, lang });
+    }
+    let mut positional = positional.into_iter();
+    let command = match positional.next() {
+        None => Command::Run { names: Vec::new(), all: true, filter, output, faults, isolate },
+        Some(command) => match command.as_str() {
+            "list" => Command::List { filter },
+            "run" => {
+                let names: Vec<String> = positional.by_ref().collect();
+                if names.is_empty() && !all && filter.is_none() {
+                    return Err(UsageError::NothingToRun);
+                }
+                if !names.is_empty() && all {
+                    return Err(UsageError::NamesWithAll);
+                }
+                Command::Run { names, all, filter, output, faults, isolate }
+            }
+            "numbers" => match positional.next() {
+                Some(path) => Command::Numbers { path: PathBuf::from(path), keep_going },
+                None => return Err(UsageError::MissingArgument("<path>")),
+            },
+            "rules" => match (positional.next(), positional.next()) {
+                (Some(rules), Some(numbers)) => Command::Rules { rules: rules.into(), numbers: numbers.into() },
+                (None, _) => return Err(UsageError::MissingArgument("<rules-file>")),
+                (Some(_), None) => return Err(UsageError::MissingArgument("<numbers-file>")),
+            },
+            "i18n" => match positional.next().as_deref() {
+                Some("check") => Command::I18nCheck,
+                Some(other) => return Err(UsageError::UnknownCommand(format!("i18n {}", other))),
+                None => return Err(UsageError::UnknownCommand(command)),
+            },
+            "snapshot" => Command::Snapshot { update: update_snapshots },
+            "explain" => Command::Explain { code: positional.next() },
+            "help" => Command::Help,
+            _ => return Err(UsageError::UnknownCommand(command)),
+        },
+    };
+    if let Some(extra) = positional.next() {
+        return Err(UsageError::UnexpectedArgument(extra));
+    }
+    match run_only {
+        Some(flag) if !matches!(command, Command::Run { .. }) => Err(UsageError::RunOnly(flag)),
+        _ => Ok(Cli { command, lang }),
+    }
+}
+
+// Resolve the demos a command refers to, keeping teaching order for --all;
+// a filter that leaves nothing is an error rather than a silent no-op
+pub fn select<'r>(
+    registry: &'r Registry,
+    names: &[String],
+    all: bool,
+    filter: Option<&str>,
+) -> Result<Vec<&'r dyn Demo>, UsageError> {
+    let mut selected: Vec<&dyn Demo> = if all || names.is_empty() {
+        registry.iter().collect()
+    } else {
+        names.iter().map(|name| find(registry, name)).collect::<Result<_, _>>()?
+    };
+    if let Some(filter) = filter {
+        selected.retain(|d| demo::matches(*d, filter));
+        if selected.is_empty() {
+            return Err(UsageError::NoMatch(filter.to_string()));
+        }
+    }
+    Ok(selected)
+}
+
+fn find<'r>(registry: &'r Registry, name: &str) -> Result<&'r dyn Demo, UsageError> {
+    registry.get(name).ok_or_else(|| UsageError::UnknownDemo {
+        name: name.to_string(),
+        suggestions: suggest(registry, name),
+    })
+}
+
+// Names that are a close edit or share a substring with the unknown one
+fn suggest(registry: &Registry, name: &str) -> Vec<&'static str> {
+    registry
+        .names()
+        .filter(|candidate| {
+            let overlaps = name.len() >= 3 && (candidate.contains(name) || name.contains(candidate));
+            overlaps || edit_distance(name, candidate) <= 3
+        })
+        .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+fn list(registry: &Registry, filter: Option<&str>) -> Result<(), UsageError> {
+    for demo in select(registry, &[], true, filter)? {
+        println!("{:<16} {}", demo.name(), demo.title());
+        println!("{:<16} {} [{}]", "", demo.description(), demo.tags().join(", "));
+    }
+    Ok(())
+}
+
+// Run the selected demos, continuing past failures; the first failure decides the exit code
+fn run(
+    registry: &Registry,
+    names: &[String],
+    all: bool,
+    filter: Option<&str>,
+    output: Output,
+    faults: Plan,
+    isolate: bool,
+) -> Result<Outcome, UsageError> {
+    let demos = select(registry, names, all, filter)?;
+    if isolate {
+        return Ok(run_isolated(&demos, output, &faults));
+    }
+    let mut ctx = Context::default();
+    if !faults.is_empty() {
+        ctx.fs = Box::new(FaultyFs::new(ctx.fs, faults));
+    }
+    let mut outcome = Outcome::Success;
+    for (i, demo) in demos.into_iter().enumerate() {
+        let result = match output {
+            Output::Text => {
+                if i > 0 {
+                    println!();
+                }
+                println!("=== {} ===", demo.title());
+                let result = demo::run_guarded(demo, &mut ctx);
+                if let Err(e) = &result {
+                    print_failure(codes::of(e.as_ref()), Category::of(e.as_ref()), &chain::plain(e.as_ref()));
+                }
+                result
+            }
+            Output::Json => {
+                let (out, err) = ctx.capture();
+                let started = Instant::now();
+                let result = demo::run_guarded(demo, &mut ctx);
+                println!("{}", demo_record(demo, &result, started, out.lines(), err.lines()));
+                result
+            }
+        };
+        if let (Err(e), Outcome::Success) = (&result, &outcome) {
+            outcome = Outcome::Exit(exit::for_error(e.as_ref()));
+        }
+    }
+    Ok(outcome)
+}
+
+// Like `run`, but every demo runs in a child process of its own, so one that aborts or
+// calls `process::exit` cannot stop the rest. Fault counts start over in every child
+fn run_isolated(demos: &[&dyn Demo], output: Output, faults: &Plan) -> Outcome {
+    let mut args = vec!["--lang".to_string(), i18n::locale().tag().to_string()];
+    args.extend(faults.args());
+
+    let mut outcome = Outcome::Success;
+    let mut report = Vec::new();
+    for (i, demo) in demos.iter().enumerate() {
+        if i > 0 && output == Output::Text {
+            println!();
+        }
+        let started = Instant::now();
+        let result: Result<(), Box<dyn Error>> = match isolate::run(demo.name(), &args) {
+            Ok(child) if output == Output::Json => {
+                let isolation = isolation_record(&child);
+                let result = child.result.map_err(Box::from);
+                let record = match child.record {
+                    Some(record) => {
+                        // Anything printed besides the record goes to stderr, so stdout stays JSON Lines
+                        eprint!("{}{}", child.stdout, child.stderr);
+                        record
+                    }
+                    // A child that died before printing its JSON record gets one written for it
+                    None => demo_record(*demo, &result, started, lines(&child.stdout), lines(&child.stderr)),
+                };
+                println!("{}", record.with("isolation", isolation));
+                result
+            }
+            Ok(child) => {
+                show_child(*demo, &child);
+                child.result.map_err(Box::from)
+            }
+            Err(e) => {
+                let context = tf("isolate.spawn_failed", &[("demo", &demo.name())]);
+                let err = DemoError::Context { context, source: Box::new(e.into()) };
+                eprintln!("{}[{}]: {}", t("cli.error"), err.code(), chain::plain(&err));
+                Err(err.into())
+            }
+        };
+        if let (Err(e), Outcome::Success) = (&result, &outcome) {
+            outcome = Outcome::Exit(exit::for_error(e.as_ref()));
+        }
+        report.push((demo.name(), result));
+    }
+
+    if output == Output::Text {
+        let failed = report.iter().filter(|(_, r)| r.is_err()).count();
+        println!("\n=== {} ===", tf("isolate.report", &[("failed", &failed), ("total", &report.len())]));
+        for (name, result) in &report {
+            match result {
+                Ok(()) => println!("{:<20} {}", name, t("isolate.ok")),
+                Err(e) => {
+                    let code = codes::of(e.as_ref()).unwrap_or("-");
+                    println!("{:<20} [{}] ({}) {}", name, code, Category::of(e.as_ref()), e);
+                }
+            }
+        }
+    }
+    outcome
+}
+
+// Lay out a child's JSON record the way a text-mode `learn run` prints the demo
+fn show_child(demo: &dyn Demo, child: &isolate::Finished) {
+    let record = child.record.as_ref();
+    let strings = |key| {
+        let items = record.and_then(|r| r.get(key)).and_then(Value::as_array).unwrap_or_default();
+        items.iter().filter_map(Value::as_str)
+    };
+    println!("=== {} ===", demo.title());
+    for line in strings("stdout") {
+        println!("{}", line);
+    }
+    print!("{}", child.stdout);
+    for line in strings("stderr") {
+        eprintln!("{}", line);
+    }
+    eprint!("{}", child.stderr);
+    if let Some(error) = record.and_then(|r| r.get("error")) {
+        let field = |key| error.get(key).and_then(Value::as_str);
+        let category = field("category").and_then(Category::from_id).unwrap_or(Category::Bug);
+        print_failure(field("code"), category, field("plain").unwrap_or_default());
+    }
+}
+
+fn lines(text: &str) -> Vec<String> {
+    text.lines().map(str::to_string).collect()
+}
+
+// The last line a text-mode `learn run` prints for a demo that failed
+fn print_failure(code: Option<&str>, category: Category, plain: &str) {
+    let code = code.map_or(String::new(), |c| format!(" [{}]", c));
+    eprintln!("{}{} ({}): {}", t("cli.demo_failed"), code, category, plain);
+}
+
+// How a demo's child process ended: `{"termination", "exit_status", "signal", "code"}`,
+// where `termination` is `ok`, `exit`, `signal` or `aborted`
+fn isolation_record(child: &isolate::Finished) -> Value {
+    let termination = isolate::Termination::of(child.status);
+    let number = |n: Option<i32>| n.map_or(Value::Null, |n| Value::Number(f64::from(n)));
+    Value::object([
+        ("termination", Value::from(termination.map_or("ok", isolate::Termination::id))),
+        ("exit_status", number(child.status.code())),
+        ("signal", number(isolate::signal(child.status))),
+        ("code", Value::from(termination.map(isolate::Termination::code))),
+    ])
+}
+
+// One JSON Lines record for a demo that ran for as long as `started` says
+fn demo_record(
+    demo: &dyn Demo,
+    result: &Result<(), Box<dyn Error>>,
+    started: Instant,
+    stdout: Vec<String>,
+    stderr: Vec<String>,
+) -> Value {
+    let elapsed = started.elapsed().as_micros() as f64 / 1000.0;
+    let mut record = vec![
+        ("name", Value::from(demo.name())),
+        ("title", Value::from(demo.title())),
+        ("status", Value::from(if result.is_ok() { "ok" } else { "failed" })),
+        ("duration_ms", Value::Number(elapsed)),
+        ("stdout", Value::from(stdout)),
+        ("stderr", Value::from(stderr)),
+    ];
+    if let Err(e) = result {
+        record.push(("error", error_record(e.as_ref())));
+    }
+    Value::object(record)
+}
+
+// `{"variant", "code", "message", "chain", "plain", "category", "exit_code"}` for a failed demo;
+// `variant` is null when the demo failed with something other than a `DemoError`. `chain` holds
+// every source, including the ones `chain::plain` leaves out, so tools see the underlying
+// `io::Error`; `plain` is the error as text-mode output shows it
+fn error_record(err: &(dyn Error + 'static)) -> Value {
+    let variant = err.downcast_ref::<DemoError>().map(DemoError::variant_name);
+    let chain: Vec<String> = chain::causes(err).skip(1).map(|e| e.to_string()).collect();
+    let class = chain::classify(err);
+    Value::object([
+        ("variant", Value::from(variant)),
+        ("code", Value::from(class.map(|c| c.code))),
+        ("message", Value::from(err.to_string())),
+        ("chain", Value::from(chain)),
+        ("plain", Value::from(chain::plain(err))),
+        ("category", Value::from(class.map_or(Category::Bug, |c| c.category).id())),
+        ("exit_code", Value::from(class.map_or(exit::SOFTWARE, |c| c.exit_code))),
+    ])
+}
+
+// Diagnostics get their caret rendering, everything else its cause chain
+fn print_error(err: &DemoError) {
+    match err {
+        DemoError::Diagnostic(d) => eprintln!("{}", d.render()),
+        e => eprintln!("{}[{}]: {}", t("cli.error"), e.code(), chain::plain(e)),
+    }
+}
+
+fn numbers(path: &Path, keep_going: bool) -> Outcome {
+    let mode = if keep_going { ErrorMode::CollectAll } else { ErrorMode::FailFast };
+    let summary = match NumberReader::open(&RealFs::new(), path).and_then(|reader| numbers::summarize(reader, mode)) {
+        Ok(summary) => summary,
+        Err(e) => {
+            print_error(&e);
+            return Outcome::Exit(exit::for_demo_error(&e));
+        }
+    };
+    for e in &summary.errors {
+        print_error(e);
+        eprintln!();
+    }
+    let none = "-".to_string();
+    println!("{:<8}{}", t("numbers.count"), summary.count);
+    println!("{:<8}{}", t("numbers.sum"), summary.sum);
+    println!("{:<8}{}", t("numbers.min"), summary.min.map_or(none.clone(), |n| n.to_string()));
+    println!("{:<8}{}", t("numbers.max"), summary.max.map_or(none, |n| n.to_string()));
+    println!("{:<8}{}", t("numbers.errors"), summary.errors.len());
+    match summary.errors.first() {
+        Some(e) => Outcome::Exit(exit::for_demo_error(e)),
+        None => Outcome::Success,
+    }
+}
+
+// Check the number in `numbers` against every rule in `rules`
+fn rules(rules: &Path, numbers: &Path) -> Outcome {
+    let fs = RealFs::new();
+    let checked = RuleSet::load(&fs, rules).and_then(|set| Ok((read_number_from_file(&fs, numbers)?, set)));
+    let (n, set) = match checked {
+        Ok(checked) => checked,
+        Err(e) => {
+            print_error(&e);
+            return Outcome::Exit(exit::for_demo_error(&e));
+        }
+    };
+    let violations = set.check(i64::from(n)).into_result().err().unwrap_or_default();
+    for v in &violations {
+        print_error(v);
+    }
+    match violations.first() {
+        Some(e) => Outcome::Exit(exit::for_demo_error(e)),
+        None => {
+            println!("{}", tf("rules.passed", &[("value", &n), ("count", &set.len())]));
+            Outcome::Success
+        }
+    }
+}
+
+// Report catalog gaps for every locale; any gap is a failure so CI can gate on it
+fn i18n_check() -> Outcome {
+    let mut complete = true;
+    for locale in Locale::ALL {
+        let missing = i18n::missing_keys(locale);
+        let unknown = i18n::unknown_keys(locale);
+        for key in &missing {
+            println!("{}", tf("i18n.missing", &[("locale", &locale.tag()), ("key", key)]));
+        }
+        for key in &unknown {
+            println!("{}", tf("i18n.unknown", &[("locale", &locale.tag()), ("key", key)]));
+        }
+        if missing.is_empty() && unknown.is_empty() {
+            let count = i18n::key_count(locale);
+            println!("{}", tf("i18n.complete", &[("locale", &locale.tag()), ("count", &count)]));
+        } else {
+            complete = false;
+        }
+    }
+    if complete { Outcome::Success } else { Outcome::Exit(exit::SOFTWARE) }
+}
+
+// Snapshots live next to Cargo.toml, wherever this is run from; a binary
+// moved away from its source tree has none to check or update
+fn snapshot(registry: &Registry, update: bool) -> Outcome {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(SNAPSHOT_DIR);
+    let checked = if dir.is_dir() {
+        snapshot::check_all(registry, &dir, update)
+    } else {
+        Err(io::Error::from(io::ErrorKind::NotFound))
+    };
+    let checked = match checked.with_context(|| tf("context.snapshot_dir", &[("path", &dir.display())])) {
+        Ok(checked) => checked,
+        Err(e) => {
+            let code = exit::for_demo_error(&e);
+            print_error(&e);
+            return Outcome::Exit(code);
+        }
+    };
+    for c in &checked {
+        let args: [(&str, &dyn Display); 3] = [("name", &c.demo), ("locale", &c.locale.tag()), ("path", &c.path.display())];
+        match &c.status {
+            Status::Matched => println!("{}", tf("snapshot.matched", &args)),
+            Status::Updated => println!("{}", tf("snapshot.updated", &args)),
+            Status::Missing => println!("{}", tf("snapshot.missing", &args)),
+            Status::Differs(diff) => println!("{}\n{}", tf("snapshot.differs", &args), diff),
+        }
+    }
+    if checked.iter().all(snapshot::Checked::passed) { Outcome::Success } else { Outcome::Exit(exit::SOFTWARE) }
+}
+
+const SNAPSHOT_DIR: &str = "snapshots";
+
+// Like `rustc --explain`: what the error means, how to trigger it and how to fix it
+fn explain(code: Option<&str>) -> Result<(), UsageError> {
+    let Some(code) = code else {
+        for entry in codes::REGISTRY {
+            println!("{}  {}", entry.code, t(entry.title));
+        }
+        return Ok(());
+    };
+    let entry = codes::lookup(code).ok_or_else(|| UsageError::UnknownCode(code.to_string()))?;
+    println!("{}: {}\n\n{}\n", entry.code, t(entry.title), t(entry.explanation));
+    println!("{}", t("explain.reproduce"));
+    for line in entry.reproducer.lines() {
+        println!("    {}", line);
+    }
+    println!("\n{}\n{}", t("explain.fix"), t(entry.fix));
+    Ok(())
+}
+
+fn help() {
+    println!("{}\n\n{}", t("cli.usage"), t("cli.exit_codes"));
+    for (code, key) in exit::CODES {
+        println!("  {:<4} {}", code, t(key));
+    }
+}
+
+// The last valid `--lang` value, looked for without parsing the rest
+fn lang_arg(args: &[String]) -> Option<Locale> {
+    args.windows(2).filter(|pair| pair[0] == "--lang").filter_map(|pair| Locale::parse(&pair[1])).next_back()
+}
+
+pub fn main<I: IntoIterator<Item = String>>(args: I) -> Outcome {
+    let args: Vec<String> = args.into_iter().collect();
+    // `--lang` applies before parsing, so usage errors come out in that language too
+    i18n::set_locale(lang_arg(&args).unwrap_or_else(Locale::from_env));
+    panic_demo::install_hook();
+    let registry = Registry::builtin();
+    let result = parse(args).and_then(|cli| {
+        match cli.command {
+            Command::List { filter } => list(&registry, filter.as_deref()).map(|_| Outcome::Success),
+            Command::Run { names, all, filter, output, faults, isolate } => {
+                run(&registry, &names, all, filter.as_deref(), output, faults, isolate)
+            }
+            Command::Numbers { path, keep_going } => Ok(numbers(&path, keep_going)),
+            Command::Rules { rules: r, numbers: n } => Ok(rules(&r, &n)),
+            Command::I18nCheck => Ok(i18n_check()),
+            Command::Snapshot { update } => Ok(snapshot(&registry, update)),
+            Command::Explain { code } => explain(code.as_deref()).map(|_| Outcome::Success),
+            Command::Help => {
+                help();
+                Ok(Outcome::Success)
+            }
+        }
+    });
+    result.unwrap_or_else(|e| {
+        eprintln!("{}: {}\n\n{}", t("cli.error"), e, t("cli.usage"));
+        Outcome::Exit(exit::USAGE)
+    })
+}
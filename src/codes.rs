@@ -0,0 +1,253 @@
+use std::error::Error;
+use std::io;
+use std::num::{IntErrorKind, ParseIntError};
+
+use crate::category::Category;
+use crate::chain;
+use crate::diagnostics::ParseIssue;
+
+// A stable error code and the catalog keys `learn explain` shows for it.
+// Codes are never reused: a retired code stays in the registry
+#[derive(Debug)]
+pub struct ErrorCode {
+    pub code: &'static str,
+    pub title: &'static str,
+    pub explanation: &'static str,
+    // Commands that produce the error; the same in every language
+    pub reproducer: &'static str,
+    pub fix: &'static str,
+}
+
+// E00xx: IO, E01xx: parsing, E02xx: business rules, E03xx: grouped errors, E04xx: panics
+pub const REGISTRY: &[ErrorCode] = &[
+    ErrorCode {
+        code: "E0001",
+        title: "code.E0001.title",
+        explanation: "code.E0001.explanation",
+        reproducer: "$ learn numbers does-not-exist.txt",
+        fix: "code.E0001.fix",
+    },
+    ErrorCode {
+        code: "E0002",
+        title: "code.E0002.title",
+        explanation: "code.E0002.explanation",
+        reproducer: "$ echo 7 > locked.txt && chmod 000 locked.txt\n$ learn numbers locked.txt",
+        fix: "code.E0002.fix",
+    },
+    ErrorCode {
+        code: "E0003",
+        title: "code.E0003.title",
+        explanation: "code.E0003.explanation",
+        reproducer: "$ learn run question-mark --inject io:timed_out@open",
+        fix: "code.E0003.fix",
+    },
+    ErrorCode {
+        code: "E0004",
+        title: "code.E0004.title",
+        explanation: "code.E0004.explanation",
+        reproducer: "$ learn run question-mark --inject io:broken_pipe@open",
+        fix: "code.E0004.fix",
+    },
+    ErrorCode {
+        code: "E0101",
+        title: "code.E0101.title",
+        explanation: "code.E0101.explanation",
+        reproducer: "$ echo odd > odd.rules && : > empty.txt\n$ learn rules odd.rules empty.txt",
+        fix: "code.E0101.fix",
+    },
+    ErrorCode {
+        code: "E0102",
+        title: "code.E0102.title",
+        explanation: "code.E0102.explanation",
+        reproducer: "$ echo 4294967296 > big.txt\n$ learn numbers big.txt",
+        fix: "code.E0102.fix",
+    },
+    ErrorCode {
+        code: "E0103",
+        title: "code.E0103.title",
+        explanation: "code.E0103.explanation",
+        reproducer: "$ echo -42 > negative.txt\n$ learn numbers negative.txt",
+        fix: "code.E0103.fix",
+    },
+    ErrorCode {
+        code: "E0104",
+        title: "code.E0104.title",
+        explanation: "code.E0104.explanation",
+        reproducer: "$ echo 12x4 > typo.txt\n$ learn numbers typo.txt",
+        fix: "code.E0104.fix",
+    },
+    ErrorCode {
+        code: "E0105",
+        title: "code.E0105.title",
+        explanation: "code.E0105.explanation",
+        reproducer: "$ learn run generic-values",
+        fix: "code.E0105.fix",
+    },
+    ErrorCode {
+        code: "E0106",
+        title: "code.E0106.title",
+        explanation: "code.E0106.explanation",
+        reproducer: "$ echo 'between 10 and' > bad.rules && echo 5 > n.txt\n$ learn rules bad.rules n.txt",
+        fix: "code.E0106.fix",
+    },
+    ErrorCode {
+        code: "E0107",
+        title: "code.E0107.title",
+        explanation: "code.E0107.explanation",
+        reproducer: "$ learn run number-lookup",
+        fix: "code.E0107.fix",
+    },
+    ErrorCode {
+        code: "E0108",
+        title: "code.E0108.title",
+        explanation: "code.E0108.explanation",
+        reproducer: "$ printf '  12\\n3x\\n' > numbers.txt && echo odd > odd.rules\n$ learn rules odd.rules numbers.txt",
+        fix: "code.E0108.fix",
+    },
+    ErrorCode {
+        code: "E0200",
+        title: "code.E0200.title",
+        explanation: "code.E0200.explanation",
+        reproducer: "let v = RuleViolation::new(\"vip_only\", \"rule.odd\", 7);\nreturn Err(DemoError::BusinessRule(v));",
+        fix: "code.E0200.fix",
+    },
+    ErrorCode {
+        code: "E0201",
+        title: "code.E0201.title",
+        explanation: "code.E0201.explanation",
+        reproducer: "$ echo odd > odd.rules && echo 42 > n.txt\n$ learn rules odd.rules n.txt",
+        fix: "code.E0201.fix",
+    },
+    ErrorCode {
+        code: "E0202",
+        title: "code.E0202.title",
+        explanation: "code.E0202.explanation",
+        reproducer: "$ echo even > even.rules && echo 7 > n.txt\n$ learn rules even.rules n.txt",
+        fix: "code.E0202.fix",
+    },
+    ErrorCode {
+        code: "E0203",
+        title: "code.E0203.title",
+        explanation: "code.E0203.explanation",
+        reproducer: "$ echo 'between 1 and 10' > range.rules && echo 42 > n.txt\n$ learn rules range.rules n.txt",
+        fix: "code.E0203.fix",
+    },
+    ErrorCode {
+        code: "E0204",
+        title: "code.E0204.title",
+        explanation: "code.E0204.explanation",
+        reproducer: "$ echo 'divisible by 7' > div.rules && echo 10 > n.txt\n$ learn rules div.rules n.txt",
+        fix: "code.E0204.fix",
+    },
+    ErrorCode {
+        code: "E0205",
+        title: "code.E0205.title",
+        explanation: "code.E0205.explanation",
+        reproducer: "$ echo 'not in 13, 42' > deny.rules && echo 13 > n.txt\n$ learn rules deny.rules n.txt",
+        fix: "code.E0205.fix",
+    },
+    ErrorCode {
+        code: "E0206",
+        title: "code.E0206.title",
+        explanation: "code.E0206.explanation",
+        reproducer: "ensure!(n <= 100, \"{} is bigger than 100\", n);",
+        fix: "code.E0206.fix",
+    },
+    ErrorCode {
+        code: "E0301",
+        title: "code.E0301.title",
+        explanation: "code.E0301.explanation",
+        reproducer: "$ learn run validation",
+        fix: "code.E0301.fix",
+    },
+    ErrorCode {
+        code: "E0302",
+        title: "code.E0302.title",
+        explanation: "code.E0302.explanation",
+        reproducer: "$ learn run retry",
+        fix: "code.E0302.fix",
+    },
+    ErrorCode {
+        code: "E0401",
+        title: "code.E0401.title",
+        explanation: "code.E0401.explanation",
+        reproducer: "$ learn run panics",
+        fix: "code.E0401.fix",
+    },
+    ErrorCode {
+        code: "E0402",
+        title: "code.E0402.title",
+        explanation: "code.E0402.explanation",
+        reproducer: "$ learn run question-mark --isolate --inject io:not_found@open",
+        fix: "code.E0402.fix",
+    },
+    ErrorCode {
+        code: "E0403",
+        title: "code.E0403.title",
+        explanation: "code.E0403.explanation",
+        reproducer: "fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {\n    unsafe { std::ptr::null_mut::<i32>().write(1) };\n}\n$ learn run <that demo> --isolate",
+        fix: "code.E0403.fix",
+    },
+    ErrorCode {
+        code: "E0404",
+        title: "code.E0404.title",
+        explanation: "code.E0404.explanation",
+        reproducer: "fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {\n    std::process::abort();\n}\n$ learn run <that demo> --isolate",
+        fix: "code.E0404.fix",
+    },
+];
+
+// Case-insensitive, so `learn explain e0102` works too
+pub fn lookup(code: &str) -> Option<&'static ErrorCode> {
+    REGISTRY.iter().find(|c| c.code.eq_ignore_ascii_case(code))
+}
+
+pub fn for_io(err: &io::Error) -> &'static str {
+    if Category::of_io(err).is_transient() {
+        return "E0003";
+    }
+    match err.kind() {
+        io::ErrorKind::NotFound => "E0001",
+        io::ErrorKind::PermissionDenied => "E0002",
+        _ => "E0004",
+    }
+}
+
+pub fn for_issue(issue: ParseIssue) -> &'static str {
+    match issue {
+        ParseIssue::Empty => "E0101",
+        ParseIssue::Overflow => "E0102",
+        ParseIssue::Negative => "E0103",
+        ParseIssue::InvalidDigit(_) => "E0104",
+        ParseIssue::Other => "E0105",
+        ParseIssue::Syntax(_) => "E0106",
+        ParseIssue::MultipleLines => "E0108",
+    }
+}
+
+// Without the offending text a negative number cannot be told from any other bad digit
+pub fn for_parse(err: &(dyn Error + 'static)) -> &'static str {
+    match err.downcast_ref::<ParseIntError>().map(ParseIntError::kind) {
+        Some(IntErrorKind::Empty) => "E0101",
+        Some(IntErrorKind::PosOverflow | IntErrorKind::NegOverflow) => "E0102",
+        Some(IntErrorKind::InvalidDigit) => "E0104",
+        _ => "E0105",
+    }
+}
+
+// By the rule's stable identifier; rules this crate does not define share E0200
+pub fn for_rule(rule: &str) -> &'static str {
+    match rule {
+        "odd" => "E0201",
+        "even" => "E0202",
+        "range" => "E0203",
+        "divisible_by" => "E0204",
+        "not_in" => "E0205",
+        _ => "E0200",
+    }
+}
+
+// For a type-erased error, see `chain::classify`
+pub fn of(err: &(dyn Error + 'static)) -> Option<&'static str> {
+    chain::classify(err).map(|c| c.code)
+}
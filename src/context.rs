@@ -0,0 +1,22 @@
+use std::fmt::Display;
+
+use crate::result_demo::DemoError;
+
+// Adds a human-readable layer on top of whatever error a `Result` carries,
+// keeping the original error reachable through `Error::source()`
+pub trait ResultExt<T> {
+    fn context<C: Display>(self, context: C) -> Result<T, DemoError>;
+
+    // Like `context`, but only builds the message when there is an error
+    fn with_context<C: Display, F: FnOnce() -> C>(self, f: F) -> Result<T, DemoError>;
+}
+
+impl<T, E: Into<DemoError>> ResultExt<T> for Result<T, E> {
+    fn context<C: Display>(self, context: C) -> Result<T, DemoError> {
+        self.map_err(|e| DemoError::Context { context: context.to_string(), source: Box::new(e.into()) })
+    }
+
+    fn with_context<C: Display, F: FnOnce() -> C>(self, f: F) -> Result<T, DemoError> {
+        self.map_err(|e| DemoError::Context { context: f().to_string(), source: Box::new(e.into()) })
+    }
+}
@@ -0,0 +1,135 @@
+use std::cell::RefCell;
+use std::error::Error;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::rc::Rc;
+
+use crate::fs::{FileSystem, RealFs};
+use crate::option_demo;
+use crate::panic_demo;
+use crate::result_demo;
+
+// Shared state handed to every demo when it runs
+pub struct Context {
+    // Where the demo prints; demos never use `println!` directly so runs can be captured
+    pub out: Box<dyn Write>,
+    pub err: Box<dyn Write>,
+    // Every file a demo reads goes through here
+    pub fs: Box<dyn FileSystem>,
+    // File the number-reading demos read from
+    pub numbers_path: PathBuf,
+    // Any existing text file, used by the IO part of the `?` demo
+    pub source_path: PathBuf,
+}
+
+// Demo paths resolve against the crate root, as `learn snapshot` does, so the
+// demos read the same files wherever `learn` is run from
+impl Default for Context {
+    fn default() -> Self {
+        Context {
+            out: Box::new(io::stdout()),
+            err: Box::new(io::stderr()),
+            fs: Box::new(RealFs::rooted(env!("CARGO_MANIFEST_DIR"))),
+            numbers_path: PathBuf::from("numbers.txt"),
+            source_path: PathBuf::from("src/main.rs"),
+        }
+    }
+}
+
+impl Context {
+    // Send further output into fresh buffers, returned as (stdout, stderr)
+    pub fn capture(&mut self) -> (Capture, Capture) {
+        let (out, err) = (Capture::default(), Capture::default());
+        self.out = Box::new(out.clone());
+        self.err = Box::new(err.clone());
+        (out, err)
+    }
+}
+
+// In-memory output stream; clones share the same buffer
+#[derive(Clone, Default)]
+pub struct Capture(Rc<RefCell<Vec<u8>>>);
+
+impl Capture {
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.0.borrow()).into_owned()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.text().lines().map(str::to_string).collect()
+    }
+}
+
+impl Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// One self-contained lesson section that tooling can enumerate and run
+pub trait Demo {
+    // Stable identifier used on the command line, e.g. `question-mark`
+    fn name(&self) -> &'static str;
+    // Section header shown before the demo runs
+    fn title(&self) -> &'static str;
+    // One-line summary of what the demo teaches
+    fn description(&self) -> &'static str;
+    fn tags(&self) -> &'static [&'static str] {
+        &[]
+    }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>>;
+}
+
+// Run `demo` with a panic turned into its error, so one broken lesson does not stop the rest
+pub fn run_guarded(demo: &dyn Demo, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+    panic_demo::catch(|| demo.run(ctx)).unwrap_or_else(|e| Err(e.into()))
+}
+
+// Ordered collection of demos; iteration order is teaching order
+#[derive(Default)]
+pub struct Registry {
+    demos: Vec<Box<dyn Demo>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Registry::default()
+    }
+
+    // Registry holding every lesson shipped with the crate
+    pub fn builtin() -> Self {
+        let mut registry = Registry::new();
+        result_demo::register(&mut registry);
+        option_demo::register(&mut registry);
+        panic_demo::register(&mut registry);
+        registry
+    }
+
+    // Panics on a duplicate name, since that is a programming error in the lesson set
+    pub fn register<D: Demo + 'static>(&mut self, demo: D) {
+        assert!(self.get(demo.name()).is_none(), "demo `{}` registered twice", demo.name());
+        self.demos.push(Box::new(demo));
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Demo> {
+        self.iter().find(|d| d.name() == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn Demo> {
+        self.demos.iter().map(|d| d.as_ref())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.iter().map(|d| d.name())
+    }
+}
+
+// Whether a demo's name, title or one of its tags contains `needle`
+pub fn matches(demo: &dyn Demo, needle: &str) -> bool {
+    demo.name().contains(needle) || demo.title().contains(needle) || demo.tags().iter().any(|t| t.contains(needle))
+}
@@ -0,0 +1,213 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::num::{IntErrorKind, ParseIntError};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::codes;
+use crate::i18n::{t, tf};
+
+// Why a value could not be parsed; integer failures are refined from
+// `ParseIntError::kind()`, every other type ends up as `Other`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseIssue {
+    Empty,
+    Negative,
+    Overflow,
+    InvalidDigit(char),
+    Other,
+    // A file that should hold one value has more than one line
+    MultipleLines,
+    // Not a value problem but a malformed line; holds the message catalog key
+    Syntax(&'static str),
+}
+
+impl ParseIssue {
+    fn classify(token: &str, err: &(dyn Error + 'static)) -> ParseIssue {
+        let Some(err) = err.downcast_ref::<ParseIntError>() else {
+            return ParseIssue::Other;
+        };
+        match err.kind() {
+            IntErrorKind::Empty => ParseIssue::Empty,
+            IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => ParseIssue::Overflow,
+            // Unsigned types report the `-` of an otherwise valid number as an invalid digit
+            IntErrorKind::InvalidDigit if is_negative_number(token) => ParseIssue::Negative,
+            IntErrorKind::InvalidDigit => match first_invalid(token) {
+                Some((_, c)) => ParseIssue::InvalidDigit(c),
+                None => ParseIssue::Other,
+            },
+            _ => ParseIssue::Other,
+        }
+    }
+
+    // Message for this issue when a value of type `expected` was wanted
+    pub fn message(self, expected: &str) -> String {
+        match self {
+            ParseIssue::Empty => t("diag.empty").to_string(),
+            ParseIssue::Negative => tf("diag.negative", &[("type", &expected)]),
+            ParseIssue::Overflow => tf("diag.overflow", &[("type", &expected)]),
+            ParseIssue::InvalidDigit(c) => tf("diag.invalid_digit", &[("char", &format!("{:?}", c))]),
+            ParseIssue::Other => tf("diag.other", &[("type", &expected)]),
+            ParseIssue::MultipleLines => t("diag.multiple_lines").to_string(),
+            ParseIssue::Syntax(key) => t(key).to_string(),
+        }
+    }
+}
+
+// A parse failure pinned to a place in a file, rendered like rustc does:
+//
+//   error[E0104]: invalid digit 'x'
+//    --> numbers.txt:1:3
+//     |
+//   1 | 12x4
+//     |   ^
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    // 1-based line and column (in chars) of the first offending character
+    pub line: usize,
+    pub column: usize,
+    // How many characters the caret underline spans
+    pub width: usize,
+    // The full source line the error is on
+    pub snippet: String,
+    pub issue: ParseIssue,
+    // Short name of the type that was being parsed, e.g. `u32`
+    pub expected: &'static str,
+    // The parse error behind the issue; syntax problems have none
+    pub source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl Diagnostic {
+    // Locate `err`, the result of parsing `token` as a `T`, inside `contents`.
+    // `token` must be a slice of `contents` (e.g. `contents.trim()`)
+    pub fn for_token<T>(path: &Path, contents: &str, token: &str, err: T::Err) -> Diagnostic
+    where
+        T: FromStr,
+        T::Err: Error + Send + Sync + 'static,
+    {
+        let start = token.as_ptr() as usize - contents.as_ptr() as usize;
+        let (offset, width, issue) = match token.find('\n') {
+            // A value spread over several lines is not one value; point at the second line
+            Some(newline) => {
+                let rest = &token[newline + 1..];
+                let next = rest.trim_start();
+                let offset = newline + 1 + rest.len() - next.len();
+                (offset, underline_width(next), ParseIssue::MultipleLines)
+            }
+            None => {
+                let issue = ParseIssue::classify(token, &err);
+                let (offset, width) = match issue {
+                    ParseIssue::Empty => (0, 1),
+                    ParseIssue::Negative => (0, 1),
+                    ParseIssue::InvalidDigit(_) => (first_invalid(token).map_or(0, |(i, _)| i), 1),
+                    ParseIssue::Overflow | ParseIssue::Other | ParseIssue::MultipleLines | ParseIssue::Syntax(_) => {
+                        (0, underline_width(token))
+                    }
+                };
+                (offset, width, issue)
+            }
+        };
+        let mut diag = Diagnostic::locate(path, contents, start + offset, width, issue);
+        diag.expected = type_label::<T>();
+        diag.source = Some(Box::new(err));
+        diag
+    }
+
+    // Like `for_token`, for a single `line` that is line `line_no` of its file
+    pub fn for_line<T>(path: &Path, line_no: usize, line: &str, token: &str, err: T::Err) -> Diagnostic
+    where
+        T: FromStr,
+        T::Err: Error + Send + Sync + 'static,
+    {
+        Diagnostic { line: line_no, ..Diagnostic::for_token::<T>(path, line, token, err) }
+    }
+
+    // A malformed `line` (line `line_no` of its file), underlining `token`.
+    // An empty `token` at the end of the line points just past the last character
+    pub fn syntax(path: &Path, line_no: usize, line: &str, token: &str, key: &'static str) -> Diagnostic {
+        let start = token.as_ptr() as usize - line.as_ptr() as usize;
+        let diag = Diagnostic::locate(path, line, start, underline_width(token), ParseIssue::Syntax(key));
+        Diagnostic { line: line_no, ..diag }
+    }
+
+    fn locate(path: &Path, contents: &str, at: usize, width: usize, issue: ParseIssue) -> Diagnostic {
+        let line_start = contents[..at].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = contents[at..].find('\n').map_or(contents.len(), |i| at + i);
+        Diagnostic {
+            path: path.to_path_buf(),
+            line: contents[..at].matches('\n').count() + 1,
+            column: contents[line_start..at].chars().count() + 1,
+            width,
+            snippet: contents[line_start..line_end].trim_end_matches('\r').to_string(),
+            issue,
+            expected: "",
+            source: None,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        codes::for_issue(self.issue)
+    }
+
+    pub fn render(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        format!(
+            "{}[{}]: {}\n{}--> {}:{}:{}\n{} |\n{} | {}\n{} | {}{}",
+            t("cli.error"),
+            self.code(),
+            self.issue.message(self.expected),
+            gutter,
+            self.path.display(),
+            self.line,
+            self.column,
+            gutter,
+            number,
+            self.snippet,
+            gutter,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width),
+        )
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let message = self.issue.message(self.expected);
+        write!(f, "{}:{}:{}: {}", self.path.display(), self.line, self.column, message)
+    }
+}
+
+impl Error for Diagnostic {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+    }
+}
+
+// Characters to underline for `token`: its first line, at least one caret
+fn underline_width(token: &str) -> usize {
+    token.lines().next().map_or(1, |l| l.chars().count().max(1))
+}
+
+// `core::net::ip_addr::Ipv4Addr` -> `Ipv4Addr`, for messages
+pub fn type_label<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn is_negative_number(token: &str) -> bool {
+    token.strip_prefix('-').is_some_and(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+}
+
+// Byte index and value of the first character that is not a digit,
+// ignoring one leading `+` which `str::parse` accepts
+fn first_invalid(token: &str) -> Option<(usize, char)> {
+    let digits = token.strip_prefix('+').unwrap_or(token);
+    let skipped = token.len() - digits.len();
+    match digits.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+        Some((i, c)) => Some((skipped + i, c)),
+        None if digits.is_empty() => token.chars().next().map(|c| (0, c)),
+        None => None,
+    }
+}
@@ -0,0 +1,91 @@
+//! Process exit codes, loosely following BSD `sysexits.h`.
+//!
+//! | code | meaning                                        |
+//! |------|------------------------------------------------|
+//! | 0    | success                                        |
+//! | 1    | a business rule was violated                   |
+//! | 64   | bad command line (`EX_USAGE`)                  |
+//! | 65   | input could not be parsed (`EX_DATAERR`)       |
+//! | 66   | input file does not exist (`EX_NOINPUT`)       |
+//! | 70   | error of a type we do not know (`EX_SOFTWARE`) |
+//! | 74   | any other IO failure (`EX_IOERR`)              |
+//! | 75   | transient failure, try again (`EX_TEMPFAIL`)   |
+//! | 77   | permission denied (`EX_NOPERM`)                |
+
+use std::error::Error;
+use std::io;
+use std::process::{ExitCode, Termination};
+
+use crate::category::Category;
+use crate::chain;
+use crate::result_demo::DemoError;
+
+pub const SUCCESS: u8 = 0;
+pub const BUSINESS_RULE: u8 = 1;
+pub const USAGE: u8 = 64;
+pub const DATA_ERR: u8 = 65;
+pub const NO_INPUT: u8 = 66;
+pub const SOFTWARE: u8 = 70;
+pub const IO_ERR: u8 = 74;
+pub const TEMP_FAIL: u8 = 75;
+pub const NO_PERM: u8 = 77;
+
+// Every code this crate can exit with and the catalog key describing it
+pub const CODES: &[(u8, &str)] = &[
+    (SUCCESS, "exit.success"),
+    (BUSINESS_RULE, "exit.business_rule"),
+    (USAGE, "exit.usage"),
+    (DATA_ERR, "exit.data_err"),
+    (NO_INPUT, "exit.no_input"),
+    (SOFTWARE, "exit.software"),
+    (IO_ERR, "exit.io_err"),
+    (TEMP_FAIL, "exit.temp_fail"),
+    (NO_PERM, "exit.no_perm"),
+];
+
+pub fn for_demo_error(err: &DemoError) -> u8 {
+    match err {
+        DemoError::Io(e) => for_io_error(e),
+        DemoError::Parse { .. } | DemoError::MissingKey(_) | DemoError::Diagnostic(_) => DATA_ERR,
+        DemoError::BusinessRule(_) | DemoError::Message(_) => BUSINESS_RULE,
+        DemoError::Context { source, .. } => for_demo_error(source),
+        // The first failure decides, as when running several demos
+        DemoError::Multiple(errors) => errors.first().map_or(SOFTWARE, for_demo_error),
+        DemoError::Panicked { .. } => SOFTWARE,
+        DemoError::Isolated { termination, .. } => termination.exit_code(),
+        // The attempt that made us give up decides
+        DemoError::RetriesExhausted { attempts, .. } => attempts.last().map_or(SOFTWARE, for_demo_error),
+    }
+}
+
+pub fn for_io_error(err: &io::Error) -> u8 {
+    if Category::of_io(err).is_transient() {
+        return TEMP_FAIL;
+    }
+    match err.kind() {
+        io::ErrorKind::NotFound => NO_INPUT,
+        io::ErrorKind::PermissionDenied => NO_PERM,
+        _ => IO_ERR,
+    }
+}
+
+// Exit code for a type-erased error, see `chain::classify`; anything unrecognised is `SOFTWARE`
+pub fn for_error(err: &(dyn Error + 'static)) -> u8 {
+    chain::classify(err).map_or(SOFTWARE, |c| c.exit_code)
+}
+
+// What `main` returns; turns into the exit code documented above
+pub enum Outcome {
+    Success,
+    // The failure has already been shown to the user, only the status is left
+    Exit(u8),
+}
+
+impl Termination for Outcome {
+    fn report(self) -> ExitCode {
+        match self {
+            Outcome::Success => ExitCode::SUCCESS,
+            Outcome::Exit(code) => ExitCode::from(code),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::{Display, Formatter};
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::fs::FileSystem;
+
+// Names accepted after `io:`, as written on the command line
+pub const KINDS: &[(&str, io::ErrorKind)] = &[
+    ("not_found", io::ErrorKind::NotFound),
+    ("permission_denied", io::ErrorKind::PermissionDenied),
+    ("interrupted", io::ErrorKind::Interrupted),
+    ("unexpected_eof", io::ErrorKind::UnexpectedEof),
+    ("invalid_data", io::ErrorKind::InvalidData),
+    ("timed_out", io::ErrorKind::TimedOut),
+    ("would_block", io::ErrorKind::WouldBlock),
+    ("broken_pipe", io::ErrorKind::BrokenPipe),
+    ("other", io::ErrorKind::Other),
+];
+
+// What a file read with `Fault::Parse` contains instead of its real contents
+pub const GARBLED: &str = "4x2\n";
+
+// The call an IO fault is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Site {
+    Open,
+    Read,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    Io { kind: io::ErrorKind, site: Site },
+    // The file opens (even if it does not exist) and reads as `GARBLED`
+    Parse,
+}
+
+// A fault and how many more times it fires; `None` fires on every open
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Injection {
+    pub fault: Fault,
+    pub times: Option<usize>,
+}
+
+impl Injection {
+    // `io:<kind>@open`, `io:<kind>@read` or `parse`, each optionally followed by `*<n>`
+    pub fn parse(spec: &str) -> Option<Injection> {
+        let (fault, times) = match spec.split_once('*') {
+            Some((fault, n)) => (fault, Some(n.parse().ok()?)),
+            None => (spec, None),
+        };
+        let fault = match fault.strip_prefix("io:") {
+            Some(io) => {
+                let (kind, site) = io.split_once('@')?;
+                let kind = KINDS.iter().find(|(name, _)| *name == kind)?.1;
+                let site = match site {
+                    "open" => Site::Open,
+                    "read" => Site::Read,
+                    _ => return None,
+                };
+                Fault::Io { kind, site }
+            }
+            None if fault == "parse" => Fault::Parse,
+            None => return None,
+        };
+        Some(Injection { fault, times })
+    }
+}
+
+// Written back the way `parse` reads it
+impl Display for Injection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.fault {
+            Fault::Io { kind, site } => {
+                let name = KINDS.iter().find(|(_, k)| *k == kind).map_or("other", |(name, _)| name);
+                let site = match site {
+                    Site::Open => "open",
+                    Site::Read => "read",
+                };
+                write!(f, "io:{}@{}", name, site)?;
+            }
+            Fault::Parse => write!(f, "parse")?,
+        }
+        match self.times {
+            Some(n) => write!(f, "*{}", n),
+            None => Ok(()),
+        }
+    }
+}
+
+// Which faults a run should inject
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Plan {
+    // Tried in order on every open; the first one that still has uses left fires
+    pub injections: Vec<Injection>,
+    // Seed for random faults on top of the injections
+    pub chaos: Option<u64>,
+}
+
+impl Plan {
+    pub fn is_empty(&self) -> bool {
+        self.injections.is_empty() && self.chaos.is_none()
+    }
+
+    // The `--inject` and `--chaos` flags that recreate this plan
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        for injection in &self.injections {
+            args.push("--inject".to_string());
+            args.push(injection.to_string());
+        }
+        if let Some(seed) = self.chaos {
+            args.push("--chaos".to_string());
+            args.push(seed.to_string());
+        }
+        args
+    }
+}
+
+// Wraps another filesystem and makes its opens and reads fail on purpose
+pub struct FaultyFs {
+    inner: Box<dyn FileSystem>,
+    injections: RefCell<Vec<Injection>>,
+    chaos: Option<Rng>,
+}
+
+impl FaultyFs {
+    pub fn new(inner: Box<dyn FileSystem>, plan: Plan) -> Self {
+        FaultyFs { inner, injections: RefCell::new(plan.injections), chaos: plan.chaos.map(Rng::new) }
+    }
+
+    fn next_fault(&self) -> Option<Fault> {
+        let mut injections = self.injections.borrow_mut();
+        if let Some(injection) = injections.iter_mut().find(|i| i.times != Some(0)) {
+            if let Some(n) = &mut injection.times {
+                *n -= 1;
+            }
+            return Some(injection.fault);
+        }
+        self.chaos.as_ref().and_then(Rng::fault)
+    }
+}
+
+impl FileSystem for FaultyFs {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read>> {
+        match self.next_fault() {
+            None => self.inner.open(path),
+            Some(Fault::Parse) => Ok(Box::new(io::Cursor::new(GARBLED))),
+            Some(Fault::Io { kind, site: Site::Open }) => Err(kind.into()),
+            Some(Fault::Io { kind, site: Site::Read }) => {
+                // The file still has to open for a read to fail
+                self.inner.open(path)?;
+                Ok(Box::new(FailingRead(kind)))
+            }
+        }
+    }
+
+    // Faults are only injected into reads
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        self.inner.write(path, contents)
+    }
+}
+
+// Fails the first read with the given kind. `read_to_string` and `read_to_end`
+// are overridden because std retries `Interrupted` there, which would hide the fault
+struct FailingRead(io::ErrorKind);
+
+impl Read for FailingRead {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(self.0.into())
+    }
+
+    fn read_to_end(&mut self, _: &mut Vec<u8>) -> io::Result<usize> {
+        Err(self.0.into())
+    }
+
+    fn read_to_string(&mut self, _: &mut String) -> io::Result<usize> {
+        Err(self.0.into())
+    }
+}
+
+// splitmix64: tiny, seedable and good enough to pick faults, so no `rand` needed
+pub(crate) struct Rng(Cell<u64>);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Rng(Cell::new(seed))
+    }
+
+    pub(crate) fn next(&self) -> u64 {
+        let state = self.0.get().wrapping_add(0x9e37_79b9_7f4a_7c15);
+        self.0.set(state);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    // Uniform in 0..1, from the top 53 bits
+    pub(crate) fn unit(&self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Roughly one open in three goes wrong, in any of the ways an injection could
+    fn fault(&self) -> Option<Fault> {
+        if self.below(3) != 0 {
+            return None;
+        }
+        let kind = KINDS[self.below(KINDS.len())].1;
+        match self.below(3) {
+            0 => Some(Fault::Io { kind, site: Site::Open }),
+            1 => Some(Fault::Io { kind, site: Site::Read }),
+            _ => Some(Fault::Parse),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+// Where the demos read their files from, so a run can be pointed at a real
+// directory or at files that exist only in memory
+pub trait FileSystem {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read>>;
+
+    // Create or replace the file at `path`
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()>;
+
+    fn read_to_string(&self, path: &Path) -> io::Result<String> {
+        let mut buf = String::new();
+        self.open(path)?.read_to_string(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+// The disk; relative paths resolve against `root` when one is set, otherwise
+// against the current directory
+#[derive(Debug, Clone, Default)]
+pub struct RealFs {
+    root: Option<PathBuf>,
+}
+
+impl RealFs {
+    pub fn new() -> Self {
+        RealFs::default()
+    }
+
+    pub fn rooted(root: impl Into<PathBuf>) -> Self {
+        RealFs { root: Some(root.into()) }
+    }
+
+    fn resolve(&self, path: &Path) -> PathBuf {
+        match &self.root {
+            Some(root) => root.join(path),
+            None => path.to_path_buf(),
+        }
+    }
+}
+
+impl FileSystem for RealFs {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read>> {
+        Ok(Box::new(File::open(self.resolve(path))?))
+    }
+
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        fs::write(self.resolve(path), contents)
+    }
+}
+
+// What opening a path in a `MemoryFs` does
+#[derive(Debug, Clone)]
+enum Entry {
+    Contents(Vec<u8>),
+    // Opening fails, e.g. with `PermissionDenied`
+    Fails(io::ErrorKind),
+    // Reading yields the bytes, then fails instead of reaching the end
+    Partial(Vec<u8>, io::ErrorKind),
+}
+
+// Files that exist only in memory; any path not added is `NotFound`.
+// Contents are raw bytes, so invalid UTF-8 stands in for a corrupted file
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFs {
+    entries: RefCell<HashMap<PathBuf, Entry>>,
+}
+
+impl MemoryFs {
+    pub fn new() -> Self {
+        MemoryFs::default()
+    }
+
+    pub fn file(mut self, path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) -> Self {
+        self.entries.get_mut().insert(path.into(), Entry::Contents(contents.into()));
+        self
+    }
+
+    pub fn failing(mut self, path: impl Into<PathBuf>, kind: io::ErrorKind) -> Self {
+        self.entries.get_mut().insert(path.into(), Entry::Fails(kind));
+        self
+    }
+
+    pub fn partial(mut self, path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>, kind: io::ErrorKind) -> Self {
+        self.entries.get_mut().insert(path.into(), Entry::Partial(contents.into(), kind));
+        self
+    }
+}
+
+impl FileSystem for MemoryFs {
+    fn open(&self, path: &Path) -> io::Result<Box<dyn Read>> {
+        match self.entries.borrow().get(path) {
+            None => Err(io::ErrorKind::NotFound.into()),
+            Some(Entry::Fails(kind)) => Err((*kind).into()),
+            Some(Entry::Contents(bytes)) => Ok(Box::new(io::Cursor::new(bytes.clone()))),
+            Some(Entry::Partial(bytes, kind)) => Ok(Box::new(PartialRead { bytes: bytes.clone(), at: 0, kind: *kind })),
+        }
+    }
+
+    // A path set up to fail keeps failing; writing does not repair it
+    fn write(&self, path: &Path, contents: &[u8]) -> io::Result<()> {
+        let mut entries = self.entries.borrow_mut();
+        match entries.get(path) {
+            Some(Entry::Fails(kind)) => Err((*kind).into()),
+            _ => {
+                entries.insert(path.to_path_buf(), Entry::Contents(contents.to_vec()));
+                Ok(())
+            }
+        }
+    }
+}
+
+struct PartialRead {
+    bytes: Vec<u8>,
+    at: usize,
+    kind: io::ErrorKind,
+}
+
+impl Read for PartialRead {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let rest = &self.bytes[self.at..];
+        if rest.is_empty() {
+            return Err(self.kind.into());
+        }
+        let n = rest.len().min(buf.len());
+        buf[..n].copy_from_slice(&rest[..n]);
+        self.at += n;
+        Ok(n)
+    }
+}
@@ -0,0 +1,689 @@
+use std::env;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// Languages the message catalog is available in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    En,
+    ZhCn,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::ZhCn];
+
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::ZhCn => "zh-CN",
+        }
+    }
+
+    // Accepts both `--lang` values (`en`, `zh-CN`) and POSIX locales (`zh_CN.UTF-8`)
+    pub fn parse(s: &str) -> Option<Locale> {
+        let lang = s.split(['.', '@']).next().unwrap_or("").to_ascii_lowercase();
+        match lang.split(['-', '_']).next() {
+            Some("en") => Some(Locale::En),
+            Some("zh") => Some(Locale::ZhCn),
+            _ => None,
+        }
+    }
+
+    // Locale from `LANG`; the lessons were written in Chinese, so that is the default
+    pub fn from_env() -> Locale {
+        env::var("LANG").ok().and_then(|v| Locale::parse(&v)).unwrap_or(Locale::ZhCn)
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::En => EN,
+            Locale::ZhCn => ZH_CN,
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(Locale::ZhCn as u8);
+
+pub fn set_locale(locale: Locale) {
+    CURRENT.store(locale as u8, Ordering::Relaxed);
+}
+
+pub fn locale() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        x if x == Locale::En as u8 => Locale::En,
+        _ => Locale::ZhCn,
+    }
+}
+
+// Message for `key` in the current locale
+pub fn t(key: &'static str) -> &'static str {
+    lookup(locale(), key)
+}
+
+// Message for `key` with `{name}` placeholders replaced by `args`
+pub fn tf(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    interpolate(t(key), args)
+}
+
+// Falls back to English, then to the key itself so a gap never hides output
+pub fn lookup(locale: Locale, key: &'static str) -> &'static str {
+    find(locale, key).or_else(|| find(Locale::En, key)).unwrap_or(key)
+}
+
+fn find(locale: Locale, key: &str) -> Option<&'static str> {
+    locale.catalog().iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+// One pass over the template, so placeholders inside a substituted value stay as they are
+pub fn interpolate(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let value = tail.find('}').and_then(|end| {
+            let name = &tail[1..end];
+            args.iter().find(|(n, _)| *n == name).map(|(_, v)| (end, v))
+        });
+        match value {
+            Some((end, v)) => {
+                out.push_str(&v.to_string());
+                rest = &tail[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+pub fn key_count(locale: Locale) -> usize {
+    locale.catalog().len()
+}
+
+// Keys English has but `locale` lacks; these silently fall back to English
+pub fn missing_keys(locale: Locale) -> Vec<&'static str> {
+    EN.iter().map(|(k, _)| *k).filter(|k| find(locale, k).is_none()).collect()
+}
+
+// Keys only `locale` has; these have no English fallback and are likely typos
+pub fn unknown_keys(locale: Locale) -> Vec<&'static str> {
+    locale.catalog().iter().map(|(k, _)| *k).filter(|k| find(Locale::En, k).is_none()).collect()
+}
+
+const EN: &[(&str, &str)] = &[
+    // command line
+    (
+        "cli.usage",
+        "Usage:
+  learn list [--filter <substring>]
+  learn run <name>... [--filter <substring>] [--output text|json]
+  learn run --all [--filter <substring>] [--output text|json]
+            [--inject <fault>]... [--chaos <seed>] [--isolate]
+  learn numbers <path> [--keep-going]
+  learn rules <rules-file> <numbers-file>
+  learn i18n check
+  learn snapshot [--update-snapshots]
+  learn explain [<code>]
+
+Faults (run only):
+  io:<kind>@open, io:<kind>@read   fail opening or reading a file with an io::ErrorKind
+  parse                            files read as text that is not a number
+  append *<n> to fire only n times; kinds: not_found, permission_denied,
+  interrupted, unexpected_eof, invalid_data, timed_out, would_block, broken_pipe, other
+
+Isolation (run only):
+  --isolate   run each demo in a child process of its own, so one that aborts
+              or exits cannot stop the rest; a report of how each ended follows
+
+Global options:
+  --lang <en|zh-CN>   output language (default: from $LANG)
+
+Running without arguments is the same as `learn run --all`.",
+    ),
+    ("cli.exit_codes", "Exit codes:"),
+    ("cli.error", "error"),
+    ("cli.demo_failed", "demo failed"),
+    ("cli.unknown_command", "unknown command `{name}`"),
+    ("cli.unknown_flag", "unknown option `{name}`"),
+    ("cli.unexpected_argument", "unexpected argument `{name}`"),
+    ("cli.missing_value", "option `{name}` needs a value"),
+    ("cli.missing_argument", "missing argument {name}"),
+    ("cli.unknown_locale", "unknown language `{name}`, expected `en` or `zh-CN`"),
+    ("cli.unknown_output", "unknown output format `{name}`, expected `text` or `json`"),
+    ("cli.unknown_fault", "unknown fault `{name}`, expected `io:<kind>@open`, `io:<kind>@read` or `parse`"),
+    ("cli.invalid_seed", "chaos seed `{name}` is not a non-negative integer"),
+    ("cli.unknown_code", "unknown error code `{name}`, run `learn explain` to list them"),
+    ("cli.nothing_to_run", "name the demos to run, or pass --all"),
+    ("cli.names_with_all", "name the demos to run or pass --all, not both"),
+    ("cli.run_only", "`{name}` only applies to `learn run`"),
+    ("cli.no_match", "no demo matches `{name}`, run `learn list` to see them all"),
+    ("cli.unknown_demo", "unknown demo `{name}`"),
+    ("cli.did_you_mean", ", did you mean: {names}"),
+    ("numbers.count", "count"),
+    ("numbers.sum", "sum"),
+    ("numbers.min", "min"),
+    ("numbers.max", "max"),
+    ("numbers.errors", "errors"),
+    ("i18n.missing", "{locale}: missing `{key}` (falls back to English)"),
+    ("i18n.unknown", "{locale}: `{key}` does not exist in English"),
+    ("i18n.complete", "{locale}: {count} keys, complete"),
+    ("snapshot.matched", "ok       {name} ({locale})"),
+    ("snapshot.updated", "updated  {name} ({locale}) -> {path}"),
+    ("snapshot.missing", "missing  {name} ({locale}): no {path}, rerun with --update-snapshots"),
+    ("snapshot.differs", "changed  {name} ({locale}) differs from {path}:"),
+    // exit codes
+    ("exit.success", "success"),
+    ("exit.business_rule", "a business rule was violated"),
+    ("exit.usage", "bad command line"),
+    ("exit.data_err", "input could not be parsed"),
+    ("exit.no_input", "input file does not exist"),
+    ("exit.software", "error of an unknown type"),
+    ("exit.io_err", "other IO error"),
+    ("exit.temp_fail", "temporary failure, trying again may work"),
+    ("category.user", "invalid input"),
+    ("category.environment", "environment problem"),
+    ("category.domain", "business rule"),
+    ("category.bug", "unexpected error"),
+    ("category.transient", "temporary failure"),
+    ("exit.no_perm", "permission denied"),
+    // DemoError
+    ("error.io", "IO error"),
+    ("error.parse", "Parse error"),
+    ("error.parse_expected", "Parse error, expected {type}"),
+    ("error.missing_key", "no number for key `{key}`"),
+    ("error.panicked", "panicked at {location}: {message}"),
+    ("isolate.exit", "demo `{demo}` exited with status {code}"),
+    ("isolate.signal", "demo `{demo}` was killed by signal {signal}"),
+    ("isolate.aborted", "demo `{demo}` aborted"),
+    ("isolate.reported", "{ended} after failing with {code}"),
+    ("isolate.spawn_failed", "starting a child process for demo `{demo}`"),
+    ("isolate.report", "Isolated run: {failed} of {total} demos failed"),
+    ("isolate.ok", "ok"),
+    ("error.business", "Business error"),
+    ("error.multiple", "{count} errors"),
+    ("error.retry.max_attempts", "gave up after {count} attempts"),
+    ("error.retry.deadline", "gave up after {count} attempts, the deadline would have passed"),
+    ("error.retry.permanent", "gave up after {count} attempts on an error retrying cannot fix"),
+    ("chain.caused_by", "caused by"),
+    ("report.backtrace", "stack backtrace:"),
+    ("diag.empty", "the file contains no number"),
+    ("diag.negative", "{type} cannot be negative"),
+    ("diag.overflow", "number does not fit in {type}"),
+    ("diag.invalid_digit", "invalid digit {char}"),
+    ("diag.other", "not a valid {type}"),
+    ("diag.multiple_lines", "expected a single value, found more than one line"),
+    ("context.open_numbers", "opening numbers file `{path}`"),
+    ("context.read_numbers", "reading numbers file `{path}`"),
+    ("context.open_rules", "reading rules file `{path}`"),
+    ("context.snapshot_dir", "opening snapshot directory `{path}`"),
+    ("context.parse_number", "parsing `{text}` as a number"),
+    // business rules
+    ("rule.odd", "the number must not be even, got {value}"),
+    ("rule.even", "the number must be even, got {value}"),
+    ("rule.range", "{value} is out of range, expected {expected}"),
+    ("rule.divisible_by", "{value} is not {expected}"),
+    ("rule.not_in", "{value} is blacklisted"),
+    ("rules.unknown_rule", "unknown rule, expected odd, even, between, divisible by or not in"),
+    ("rules.expected_number", "expected a number"),
+    ("rules.expected_and", "expected `and`"),
+    ("rules.expected_by", "expected `by`"),
+    ("rules.expected_in", "expected `in`"),
+    ("rules.empty_range", "upper bound is below the lower bound"),
+    ("rules.zero_divisor", "cannot be divisible by 0"),
+    ("rules.empty_list", "the list needs at least one number"),
+    ("rules.trailing", "unexpected text after the rule"),
+    ("rules.passed", "{value} passes all {count} rules"),
+    ("constraint.odd", "an odd number"),
+    ("constraint.even", "an even number"),
+    ("constraint.range", "a number in {min}..={max}"),
+    ("constraint.divisible_by", "a multiple of {divisor}"),
+    ("constraint.not_in", "none of {values}"),
+    // result_demo
+    ("demo.basics.title", "Result basics"),
+    ("demo.basics.description", "is_ok / is_err / unwrap_or / match"),
+    ("demo.question_mark.title", "Propagating errors with ?"),
+    ("demo.question_mark.description", "? converts ParseIntError / io::Error into DemoError and returns it"),
+    ("demo.combinators.title", "map / map_err / and_then combinators"),
+    ("demo.combinators.description", "transform and chain Results without match"),
+    ("demo.combinators.parse_failed", "parse failed: {error}"),
+    ("demo.combinators.not_a_number", "not a number"),
+    ("demo.combinators.divide_by_zero", "cannot divide by 0"),
+    ("demo.custom_error.title", "Custom error types and From conversions"),
+    ("demo.custom_error.description", "read a number from a file, unifying errors as DemoError"),
+    ("demo.custom_error.ok", "read succeeded: {value}"),
+    ("demo.custom_error.failed", "read failed: {error}"),
+    ("demo.boxed_error.title", "Erasing concrete errors into Box<dyn Error>"),
+    ("demo.boxed_error.description", "the same read, with Box<dyn Error> as the error type"),
+    ("demo.boxed_error.ok", "boxed read succeeded: {value}"),
+    ("demo.boxed_error.failed", "boxed read failed: {error}"),
+    ("demo.early_return.title", "Early returns with bail!, ensure! and context!"),
+    ("demo.early_return.description", "the same checks written with `return Err(...)` and with the macros"),
+    ("demo.early_return.reading", "reading `{path}`"),
+    ("demo.early_return.too_big", "{value} is bigger than 100"),
+    ("demo.early_return.unlucky", "13 is unlucky"),
+    ("demo.option_basics.title", "Option basics"),
+    ("demo.option_basics.description", "inspect, unwrap with defaults, match, and `?` on Option"),
+    ("demo.option_combinators.title", "Option combinators"),
+    ("demo.option_combinators.description", "map, filter, zip, and_then and or"),
+    ("demo.option_take.title", "Moving values out with take and replace"),
+    ("demo.option_take.description", "take, replace and get_or_insert on an Option you own"),
+    ("demo.option_result.title", "Converting between Option and Result"),
+    ("demo.option_result.description", "ok_or, ok_or_else, ok, err and transpose"),
+    ("demo.number_lookup.title", "Turning a missing key into an error"),
+    ("demo.number_lookup.description", "a lookup whose None becomes a DemoError naming the key"),
+    ("demo.panics.title", "Panics versus Result"),
+    ("demo.panics.description", "unwrap and expect panics, caught with catch_unwind and turned into a DemoError"),
+    ("demo.recovery.title", "Recovering based on the error's type"),
+    ("demo.recovery.description", "walk a Box<dyn Error>'s chain, downcast to known types and pick a recovery"),
+    ("demo.recovery.failed", "failed: {error}"),
+    ("demo.recovery.found", "known types:"),
+    ("demo.recovery.created", "created `{path}` with the default value 0"),
+    ("demo.recovery.prompt", "please enter a number for `{path}`:"),
+    ("demo.recovery.too_many", "still failing after 5 recoveries"),
+    ("demo.report.title", "A Report type instead of Box<dyn Error>"),
+    ("demo.report.description", "context, backtraces and downcasting through the chain, compared with Box<dyn Error>"),
+    ("demo.report.unexpected_ok", "the read was expected to fail"),
+    ("demo.report.loading", "loading the lucky number"),
+    ("demo.report.downcast", "downcasting a wrapped DemoError:"),
+    ("demo.report.size", "size: Report {report} bytes, Box<dyn Error> {boxed} bytes"),
+    ("demo.error_chain.title", "Error context and the cause chain"),
+    ("demo.error_chain.description", "context() / with_context() layers, printed via Error::source()"),
+    ("demo.error_chain.loading_config", "loading server config"),
+    ("demo.diagnostics.title", "Line and column aware parse diagnostics"),
+    ("demo.diagnostics.description", "point at the offending character, like rustc does"),
+    ("demo.generic_values.title", "Reading any FromStr type"),
+    ("demo.generic_values.description", "one generic reader for i64, f64, bool, IP addresses and custom types"),
+    ("demo.generic_values.missing_unit", "temperature must end with `C`"),
+    ("demo.generic_values.below_zero", "{value} is below absolute zero"),
+    ("demo.virtual_fs.title", "Simulating file failures in memory"),
+    ("demo.virtual_fs.description", "missing, locked, truncated and corrupted files from an in-memory filesystem"),
+    ("demo.retry.title", "Retrying transient IO errors with backoff"),
+    ("demo.retry.description", "a retry policy with exponential backoff, jitter and a deadline around the file read"),
+    ("demo.retry.recovers", "times out twice, then succeeds:"),
+    ("demo.retry.gives_up", "interrupted on every read:"),
+    ("demo.retry.permanent", "file does not exist (not retried):"),
+    ("demo.retry.deadline", "would block, 40ms apart with a 50ms deadline:"),
+    ("demo.retry.attempt_failed", "attempt {attempt} failed: {error}; retrying in {delay}"),
+    ("demo.business_rule.title", "Structured business-rule errors"),
+    ("demo.business_rule.description", "rule id, offending value, expected constraint and a stable code"),
+    ("demo.rules_engine.title", "Business rules from a rules file"),
+    ("demo.rules_engine.description", "parse declarative rules and report every rule a number breaks"),
+    ("demo.rules_engine.violations", "{count} rule(s) broken"),
+    ("demo.validation.title", "Fail fast with ? versus collecting every error"),
+    ("demo.validation.description", "Validated zips independent checks and keeps all of their errors"),
+    ("demo.validation.fail_fast", "fail fast (?):"),
+    ("demo.validation.accumulate", "accumulate (Validated):"),
+    ("demo.validation.field", "field `{name}`"),
+    // error codes, see `learn explain`
+    ("explain.reproduce", "Reproduce:"),
+    ("explain.fix", "Fix:"),
+    ("code.E0001.title", "input file does not exist"),
+    ("code.E0001.explanation", "A file the command needs to read could not be found. Relative paths are resolved against the current directory, so the same command can work in one directory and fail in another."),
+    ("code.E0001.fix", "Check the path for typos, run the command from the directory that holds the file, or pass an absolute path."),
+    ("code.E0002.title", "permission denied"),
+    ("code.E0002.explanation", "The file exists, but the current user is not allowed to open it."),
+    ("code.E0002.fix", "Give yourself read access (for example `chmod u+r <file>`) or run the command as a user who has it."),
+    ("code.E0003.title", "temporary IO failure"),
+    ("code.E0003.explanation", "Reading a file was interrupted, would have blocked or timed out. These failures usually go away on their own, which is why they exit with 75 (EX_TEMPFAIL)."),
+    ("code.E0003.fix", "Try again, or wrap the read in a `RetryPolicy` so it is retried automatically with backoff."),
+    ("code.E0004.title", "IO failure"),
+    ("code.E0004.explanation", "Reading a file failed for a reason that is neither a missing file, a permission problem nor a temporary condition, for example invalid UTF-8 or a broken device."),
+    ("code.E0004.fix", "Look at the innermost cause in the error chain; it names what the operating system reported."),
+    ("code.E0101.title", "the input is empty"),
+    ("code.E0101.explanation", "A number was expected, but the file contained nothing but whitespace."),
+    ("code.E0101.fix", "Put a number in the file."),
+    ("code.E0102.title", "number is too large for its type"),
+    ("code.E0102.explanation", "The text is a valid number, but it does not fit in the integer type being parsed. A `u32`, for example, stops at 4294967295."),
+    ("code.E0102.fix", "Use a smaller value, or parse into a wider type such as `u64` or `i128`."),
+    ("code.E0103.title", "negative number for an unsigned type"),
+    ("code.E0103.explanation", "The value starts with `-`, but the type being parsed cannot hold negative numbers."),
+    ("code.E0103.fix", "Remove the sign, or parse into a signed type such as `i64`."),
+    ("code.E0104.title", "invalid digit"),
+    ("code.E0104.explanation", "The text contains a character that is not a digit, such as a letter or a stray unit. The diagnostic points at the first such character."),
+    ("code.E0104.fix", "Remove or correct the character the caret points at."),
+    ("code.E0105.title", "value could not be parsed"),
+    ("code.E0105.explanation", "The text is not a valid value of the requested type, e.g. `yes` is not a `bool` and `10.0.0.300` is not an IPv4 address. The cause in the chain is the type's own parse error."),
+    ("code.E0105.fix", "Write the value in the form the type expects; the cause says which form that is."),
+    ("code.E0106.title", "malformed rules file line"),
+    ("code.E0106.explanation", "A line of a rules file is not one of `odd`, `even`, `between A and B`, `divisible by N` or `not in a, b, ...`."),
+    ("code.E0106.fix", "Rewrite the line the caret points at using one of those forms, or turn it into a `#` comment."),
+    ("code.E0107.title", "no value for key"),
+    ("code.E0107.explanation", "A lookup by key found nothing. The `None` from the lookup was turned into an error with `ok_or_else`, so the message names the missing key."),
+    ("code.E0107.fix", "Add the key to the table, or look up a key that exists."),
+    ("code.E0108.title", "more than one line where one value was expected"),
+    ("code.E0108.explanation", "The whole file is parsed as a single number, but after trimming whitespace it still spans several lines. The caret points at the first character of the second line."),
+    ("code.E0108.fix", "Keep only one number in the file, or check a file of many numbers with `learn numbers <path>`."),
+    ("code.E0200.title", "business rule violated"),
+    ("code.E0200.explanation", "A value broke a rule that is not one of the rules built into this crate. The message says which rule and what was expected."),
+    ("code.E0200.fix", "Change the value so it satisfies the rule, or give the rule its own code in `codes::REGISTRY`."),
+    ("code.E0201.title", "number must be odd"),
+    ("code.E0201.explanation", "The `odd` rule rejects even numbers."),
+    ("code.E0201.fix", "Use an odd number, or remove the `odd` rule."),
+    ("code.E0202.title", "number must be even"),
+    ("code.E0202.explanation", "The `even` rule rejects odd numbers."),
+    ("code.E0202.fix", "Use an even number, or remove the `even` rule."),
+    ("code.E0203.title", "number is out of range"),
+    ("code.E0203.explanation", "A `between A and B` rule rejects numbers below A or above B; both ends are allowed."),
+    ("code.E0203.fix", "Use a number inside the range, or widen the range."),
+    ("code.E0204.title", "number is not divisible"),
+    ("code.E0204.explanation", "A `divisible by N` rule rejects numbers that leave a remainder when divided by N."),
+    ("code.E0204.fix", "Use a multiple of N."),
+    ("code.E0205.title", "number is not allowed"),
+    ("code.E0205.explanation", "A `not in a, b, ...` rule rejects every number on its list."),
+    ("code.E0205.fix", "Use a number that is not on the list."),
+    ("code.E0206.title", "check failed"),
+    ("code.E0206.explanation", "A check written with `bail!` or `ensure!` failed. It carries only a message saying what was wrong, not a structured rule violation."),
+    ("code.E0206.fix", "Read the message and change the input it complains about."),
+    ("code.E0301.title", "several errors"),
+    ("code.E0301.explanation", "Validation collected every problem instead of stopping at the first one. Each grouped error is listed below the summary with its own cause chain."),
+    ("code.E0301.fix", "Fix each listed error; they are independent of each other."),
+    ("code.E0302.title", "gave up retrying"),
+    ("code.E0302.explanation", "An operation was retried and never succeeded: it ran out of attempts, would have passed its deadline, or hit an error that retrying cannot fix. Every attempt's error is listed."),
+    ("code.E0302.fix", "Look at the last attempt's error. If it is temporary, allow more attempts or a later deadline; otherwise fix its cause."),
+    ("code.E0401.title", "the code panicked"),
+    ("code.E0401.explanation", "Something called `unwrap`, `expect`, indexed past the end of a slice or called `panic!`. The panic was caught with `catch_unwind` and turned into an error, so the rest of the run went on."),
+    ("code.E0401.fix", "A panic is a bug. Go to the reported location and return a `Result` or handle the `None` instead of panicking."),
+    ("code.E0402.title", "isolated demo exited with an error"),
+    ("code.E0402.explanation", "With `--isolate` every demo runs in a child process. This one exited with a non-zero status, either because the demo failed (its own error is printed above the report) or because it called `std::process::exit`. The run passes the child's status on as its exit code."),
+    ("code.E0402.fix", "Run the demo on its own without `--isolate` to see its error, then fix that."),
+    ("code.E0403.title", "isolated demo was killed by a signal"),
+    ("code.E0403.explanation", "With `--isolate` every demo runs in a child process. This one never exited: a signal such as SIGSEGV or SIGKILL ended it. Without isolation it would have ended the whole run."),
+    ("code.E0403.fix", "Look for unsafe code, stack overflows or outside processes killing the demo."),
+    ("code.E0404.title", "isolated demo aborted"),
+    ("code.E0404.explanation", "With `--isolate` every demo runs in a child process. This one aborted: it called `std::process::abort`, panicked while already panicking, or was built with `panic = \"abort\"`. An abort cannot be caught like a panic, so without isolation it would have ended the whole run."),
+    ("code.E0404.fix", "Find what aborted in the demo's stderr above the report and return an error instead."),
+    ("panic.report", "the program panicked, which is a bug\n  message:  {message}\n  location: {location}\n  hint:     return a `Result` for failures the caller can handle; set RUST_BACKTRACE=1 to see how the code got there"),
+    ("panic.unknown_location", "<unknown location>"),
+    ("panic.unknown_payload", "<panic payload that is not a string>"),
+];
+
+const ZH_CN: &[(&str, &str)] = &[
+    // command line
+    (
+        "cli.usage",
+        "用法:
+  learn list [--filter <substring>]
+  learn run <name>... [--filter <substring>] [--output text|json]
+  learn run --all [--filter <substring>] [--output text|json]
+            [--inject <fault>]... [--chaos <seed>] [--isolate]
+  learn numbers <path> [--keep-going]
+  learn rules <rules-file> <numbers-file>
+  learn i18n check
+  learn snapshot [--update-snapshots]
+  learn explain [<code>]
+
+故障注入 (仅 run):
+  io:<kind>@open, io:<kind>@read   打开或读取文件时以指定的 io::ErrorKind 失败
+  parse                            文件内容读出来不是数字
+  末尾加 *<n> 表示只触发 n 次; kind 可选: not_found, permission_denied,
+  interrupted, unexpected_eof, invalid_data, timed_out, would_block, broken_pipe, other
+
+隔离 (仅 run):
+  --isolate   每个演示在独立的子进程中运行，某个演示中止或退出时不会影响其余演示；
+              最后会报告每个演示的结束方式
+
+全局选项:
+  --lang <en|zh-CN>   输出语言 (默认取自 $LANG)
+
+不带参数运行等同于 `learn run --all`。",
+    ),
+    ("cli.exit_codes", "退出码:"),
+    ("cli.error", "错误"),
+    ("cli.demo_failed", "demo 运行错误"),
+    ("cli.unknown_command", "未知命令 `{name}`"),
+    ("cli.unknown_flag", "未知选项 `{name}`"),
+    ("cli.unexpected_argument", "多余的参数 `{name}`"),
+    ("cli.missing_value", "选项 `{name}` 需要一个参数"),
+    ("cli.missing_argument", "缺少参数 {name}"),
+    ("cli.unknown_locale", "未知语言 `{name}`，可选 `en` 或 `zh-CN`"),
+    ("cli.unknown_output", "未知的输出格式 `{name}`，应为 `text` 或 `json`"),
+    ("cli.unknown_fault", "未知的故障 `{name}`，应为 `io:<kind>@open`、`io:<kind>@read` 或 `parse`"),
+    ("cli.invalid_seed", "chaos 种子 `{name}` 不是非负整数"),
+    ("cli.unknown_code", "未知的错误代码 `{name}`，运行 `learn explain` 查看全部代码"),
+    ("cli.nothing_to_run", "请指定要运行的 demo 名称，或使用 --all"),
+    ("cli.names_with_all", "请指定要运行的 demo 名称或使用 --all，二者不能同时使用"),
+    ("cli.run_only", "`{name}` 只能用于 `learn run`"),
+    ("cli.no_match", "没有与 `{name}` 匹配的 demo，运行 `learn list` 查看全部"),
+    ("cli.unknown_demo", "未知 demo `{name}`"),
+    ("cli.did_you_mean", "，你是不是想要: {names}"),
+    ("numbers.count", "个数"),
+    ("numbers.sum", "总和"),
+    ("numbers.min", "最小"),
+    ("numbers.max", "最大"),
+    ("numbers.errors", "错误"),
+    ("i18n.missing", "{locale}: 缺少 `{key}` (回退到英文)"),
+    ("i18n.unknown", "{locale}: `{key}` 在英文中不存在"),
+    ("i18n.complete", "{locale}: 共 {count} 条，完整"),
+    ("snapshot.matched", "一致     {name} ({locale})"),
+    ("snapshot.updated", "已更新   {name} ({locale}) -> {path}"),
+    ("snapshot.missing", "缺失     {name} ({locale}): 没有 {path}，请使用 --update-snapshots 重新生成"),
+    ("snapshot.differs", "有变化   {name} ({locale}) 与 {path} 不一致:"),
+    // exit codes
+    ("exit.success", "成功"),
+    ("exit.business_rule", "违反业务规则"),
+    ("exit.usage", "命令行用法错误"),
+    ("exit.data_err", "输入无法解析"),
+    ("exit.no_input", "输入文件不存在"),
+    ("exit.software", "未知类型的错误"),
+    ("exit.io_err", "其他 IO 错误"),
+    ("exit.temp_fail", "临时性故障，重试可能成功"),
+    ("category.user", "输入有误"),
+    ("category.environment", "运行环境问题"),
+    ("category.domain", "业务规则"),
+    ("category.bug", "意料之外的错误"),
+    ("category.transient", "临时性故障"),
+    ("exit.no_perm", "没有权限"),
+    // DemoError
+    ("error.io", "IO 错误"),
+    ("error.parse", "解析错误"),
+    ("error.parse_expected", "解析错误，应为 {type}"),
+    ("error.missing_key", "键 `{key}` 没有对应的数字"),
+    ("error.panicked", "在 {location} 处发生 panic: {message}"),
+    ("isolate.exit", "演示 `{demo}` 以状态码 {code} 退出"),
+    ("isolate.signal", "演示 `{demo}` 被信号 {signal} 终止"),
+    ("isolate.aborted", "演示 `{demo}` 异常中止"),
+    ("isolate.reported", "{ended}，此前以 {code} 失败"),
+    ("isolate.spawn_failed", "为演示 `{demo}` 启动子进程"),
+    ("isolate.report", "隔离运行: {total} 个演示中有 {failed} 个失败"),
+    ("isolate.ok", "成功"),
+    ("error.business", "业务错误"),
+    ("error.multiple", "共 {count} 个错误"),
+    ("error.retry.max_attempts", "尝试 {count} 次后放弃"),
+    ("error.retry.deadline", "尝试 {count} 次后放弃，再等下去会超过截止时间"),
+    ("error.retry.permanent", "尝试 {count} 次后放弃，遇到了重试也无法解决的错误"),
+    ("chain.caused_by", "原因"),
+    ("report.backtrace", "调用栈:"),
+    ("diag.empty", "文件中没有数字"),
+    ("diag.negative", "{type} 不能是负数"),
+    ("diag.overflow", "数字超出 {type} 的范围"),
+    ("diag.invalid_digit", "无效的数字字符 {char}"),
+    ("diag.other", "不是有效的 {type}"),
+    ("diag.multiple_lines", "应该只有一个值，却有多行内容"),
+    ("context.open_numbers", "打开数字文件 `{path}`"),
+    ("context.read_numbers", "读取数字文件 `{path}`"),
+    ("context.open_rules", "读取规则文件 `{path}`"),
+    ("context.snapshot_dir", "打开快照目录 `{path}`"),
+    ("context.parse_number", "把 `{text}` 解析为数字"),
+    // business rules
+    ("rule.odd", "数字不能是偶数，实际为 {value}"),
+    ("rule.even", "数字必须是偶数，实际为 {value}"),
+    ("rule.range", "{value} 超出范围，应为 {expected}"),
+    ("rule.divisible_by", "{value} 不是 {expected}"),
+    ("rule.not_in", "{value} 在黑名单中"),
+    ("rules.unknown_rule", "未知规则，可选 odd、even、between、divisible by 或 not in"),
+    ("rules.expected_number", "这里需要一个数字"),
+    ("rules.expected_and", "这里应为 `and`"),
+    ("rules.expected_by", "这里应为 `by`"),
+    ("rules.expected_in", "这里应为 `in`"),
+    ("rules.empty_range", "上界小于下界"),
+    ("rules.zero_divisor", "除数不能为 0"),
+    ("rules.empty_list", "列表中至少需要一个数字"),
+    ("rules.trailing", "规则后面有多余的内容"),
+    ("rules.passed", "{value} 通过了全部 {count} 条规则"),
+    ("constraint.odd", "奇数"),
+    ("constraint.even", "偶数"),
+    ("constraint.range", "{min}..={max} 之间的数"),
+    ("constraint.divisible_by", "{divisor} 的倍数"),
+    ("constraint.not_in", "不属于 {values} 的数"),
+    // result_demo
+    ("demo.basics.title", "Result 基本用法"),
+    ("demo.basics.description", "is_ok / is_err / unwrap_or / match"),
+    ("demo.question_mark.title", "? 运算符传播错误"),
+    ("demo.question_mark.description", "用 ? 把 ParseIntError / io::Error 转换为 DemoError 向上传播"),
+    ("demo.combinators.title", "map / map_err / and_then 组合器"),
+    ("demo.combinators.description", "不用 match 也能变换和串联 Result"),
+    ("demo.combinators.parse_failed", "解析失败: {error}"),
+    ("demo.combinators.not_a_number", "不是数字"),
+    ("demo.combinators.divide_by_zero", "除数不能为 0"),
+    ("demo.custom_error.title", "自定义错误类型与 From 转换"),
+    ("demo.custom_error.description", "从文件读取数字，错误统一为 DemoError"),
+    ("demo.custom_error.ok", "读取成功: {value}"),
+    ("demo.custom_error.failed", "读取失败: {error}"),
+    ("demo.boxed_error.title", "将具体错误抹平为 Box<dyn Error>"),
+    ("demo.boxed_error.description", "同样的读取逻辑，错误类型换成 Box<dyn Error>"),
+    ("demo.boxed_error.ok", "boxed 读取成功: {value}"),
+    ("demo.boxed_error.failed", "boxed 读取失败: {error}"),
+    ("demo.early_return.title", "用 bail!、ensure! 和 context! 提前返回"),
+    ("demo.early_return.description", "同一组检查，分别用 `return Err(...)` 和宏来写"),
+    ("demo.early_return.reading", "读取 `{path}`"),
+    ("demo.early_return.too_big", "{value} 大于 100"),
+    ("demo.early_return.unlucky", "13 不吉利"),
+    ("demo.option_basics.title", "Option 基本用法"),
+    ("demo.option_basics.description", "检查、带默认值取值、match，以及在 Option 上使用 `?`"),
+    ("demo.option_combinators.title", "Option 组合子"),
+    ("demo.option_combinators.description", "map、filter、zip、and_then 和 or"),
+    ("demo.option_take.title", "用 take 和 replace 取出值"),
+    ("demo.option_take.description", "在自己拥有的 Option 上使用 take、replace 和 get_or_insert"),
+    ("demo.option_result.title", "Option 与 Result 之间的转换"),
+    ("demo.option_result.description", "ok_or、ok_or_else、ok、err 和 transpose"),
+    ("demo.number_lookup.title", "把缺失的键变成错误"),
+    ("demo.number_lookup.description", "查找结果为 None 时，变成一个写明键名的 DemoError"),
+    ("demo.panics.title", "panic 与 Result 的对比"),
+    ("demo.panics.description", "unwrap 和 expect 引发的 panic，用 catch_unwind 捕获并转换成 DemoError"),
+    ("demo.recovery.title", "按错误类型选择恢复方式"),
+    ("demo.recovery.description", "遍历 Box<dyn Error> 的错误链，向下转型为已知类型并选择恢复方式"),
+    ("demo.recovery.failed", "失败: {error}"),
+    ("demo.recovery.found", "已知类型:"),
+    ("demo.recovery.created", "已创建 `{path}`，写入默认值 0"),
+    ("demo.recovery.prompt", "请为 `{path}` 输入一个数字:"),
+    ("demo.recovery.too_many", "恢复 5 次后仍然失败"),
+    ("demo.report.title", "用 Report 类型代替 Box<dyn Error>"),
+    ("demo.report.description", "与 Box<dyn Error> 对比: 上下文、调用栈以及沿错误链向下转型"),
+    ("demo.report.unexpected_ok", "这次读取本应失败"),
+    ("demo.report.loading", "加载幸运数字"),
+    ("demo.report.downcast", "对包裹起来的 DemoError 向下转型:"),
+    ("demo.report.size", "大小: Report {report} 字节，Box<dyn Error> {boxed} 字节"),
+    ("demo.error_chain.title", "错误上下文与 cause 链"),
+    ("demo.error_chain.description", "用 context() / with_context() 叠加上下文，再沿 Error::source() 打印"),
+    ("demo.error_chain.loading_config", "加载服务器配置"),
+    ("demo.diagnostics.title", "带行号列号的解析诊断"),
+    ("demo.diagnostics.description", "像 rustc 一样指出出错的字符"),
+    ("demo.generic_values.title", "读取任意 FromStr 类型"),
+    ("demo.generic_values.description", "同一个泛型读取函数覆盖 i64、f64、bool、IP 地址和自定义类型"),
+    ("demo.generic_values.missing_unit", "温度必须以 `C` 结尾"),
+    ("demo.generic_values.below_zero", "{value} 低于绝对零度"),
+    ("demo.virtual_fs.title", "在内存中模拟文件故障"),
+    ("demo.virtual_fs.description", "用内存文件系统模拟缺失、无权限、读取中断和内容损坏的文件"),
+    ("demo.retry.title", "对临时性 IO 错误进行退避重试"),
+    ("demo.retry.description", "用带指数退避、随机抖动和截止时间的重试策略包裹文件读取"),
+    ("demo.retry.recovers", "两次超时后成功:"),
+    ("demo.retry.gives_up", "每次读取都被中断:"),
+    ("demo.retry.permanent", "文件不存在 (不会重试):"),
+    ("demo.retry.deadline", "操作会阻塞，间隔 40ms，截止时间 50ms:"),
+    ("demo.retry.attempt_failed", "第 {attempt} 次尝试失败: {error}; {delay} 后重试"),
+    ("demo.business_rule.title", "结构化的业务规则错误"),
+    ("demo.business_rule.description", "规则标识、违规值、期望约束以及稳定的错误码"),
+    ("demo.rules_engine.title", "从规则文件加载业务规则"),
+    ("demo.rules_engine.description", "解析声明式规则，报告数字违反的每一条规则"),
+    ("demo.rules_engine.violations", "违反 {count} 条规则"),
+    ("demo.validation.title", "用 ? 快速失败 vs 收集全部错误"),
+    ("demo.validation.description", "Validated 组合互不依赖的检查，并保留所有错误"),
+    ("demo.validation.fail_fast", "快速失败 (?):"),
+    ("demo.validation.accumulate", "全部收集 (Validated):"),
+    ("demo.validation.field", "字段 `{name}`"),
+    // error codes, see `learn explain`
+    ("explain.reproduce", "复现:"),
+    ("explain.fix", "修复:"),
+    ("code.E0001.title", "输入文件不存在"),
+    ("code.E0001.explanation", "命令需要读取的文件找不到。相对路径是相对于当前目录解析的，所以同一条命令在一个目录下能运行，换个目录就可能失败。"),
+    ("code.E0001.fix", "检查路径是否拼写正确，在文件所在目录下运行命令，或者改用绝对路径。"),
+    ("code.E0002.title", "没有权限"),
+    ("code.E0002.explanation", "文件存在，但当前用户无权打开它。"),
+    ("code.E0002.fix", "为自己加上读权限 (例如 `chmod u+r <file>`)，或换成有权限的用户运行。"),
+    ("code.E0003.title", "临时性 IO 故障"),
+    ("code.E0003.explanation", "读取文件时被中断、会阻塞或超时。这类故障通常会自行消失，因此退出码是 75 (EX_TEMPFAIL)。"),
+    ("code.E0003.fix", "再试一次，或者用 `RetryPolicy` 包裹读取操作，让它带退避地自动重试。"),
+    ("code.E0004.title", "IO 故障"),
+    ("code.E0004.explanation", "读取文件失败，原因既不是文件缺失、权限问题，也不是临时状况，例如文件不是合法的 UTF-8 或设备损坏。"),
+    ("code.E0004.fix", "查看错误链中最内层的原因，它给出了操作系统报告的问题。"),
+    ("code.E0101.title", "输入为空"),
+    ("code.E0101.explanation", "需要一个数字，但文件里只有空白字符。"),
+    ("code.E0101.fix", "在文件中写入一个数字。"),
+    ("code.E0102.title", "数字超出类型范围"),
+    ("code.E0102.explanation", "文本是合法的数字，但放不进要解析的整数类型。例如 `u32` 最大只能到 4294967295。"),
+    ("code.E0102.fix", "改用更小的值，或者解析成更宽的类型，如 `u64` 或 `i128`。"),
+    ("code.E0103.title", "无符号类型遇到负数"),
+    ("code.E0103.explanation", "值以 `-` 开头，但要解析的类型不能表示负数。"),
+    ("code.E0103.fix", "去掉负号，或者解析成有符号类型，如 `i64`。"),
+    ("code.E0104.title", "非法数字字符"),
+    ("code.E0104.explanation", "文本中含有不是数字的字符，比如字母或多余的单位。诊断信息会指向第一个这样的字符。"),
+    ("code.E0104.fix", "删除或改正插入符 (^) 指向的字符。"),
+    ("code.E0105.title", "值无法解析"),
+    ("code.E0105.explanation", "文本不是目标类型的合法值，例如 `yes` 不是 `bool`，`10.0.0.300` 不是 IPv4 地址。错误链中的原因就是该类型自己的解析错误。"),
+    ("code.E0105.fix", "按照该类型要求的格式书写；错误原因会说明是什么格式。"),
+    ("code.E0106.title", "规则文件中的行格式错误"),
+    ("code.E0106.explanation", "规则文件的某一行不是 `odd`、`even`、`between A and B`、`divisible by N` 或 `not in a, b, ...` 之一。"),
+    ("code.E0106.fix", "按上述格式之一改写插入符指向的那一行，或者把它改成 `#` 注释。"),
+    ("code.E0107.title", "键没有对应的值"),
+    ("code.E0107.explanation", "按键查找没有找到任何值。查找得到的 `None` 通过 `ok_or_else` 变成了错误，因此消息中会写出缺失的键。"),
+    ("code.E0107.fix", "把这个键加入表中，或者查找一个存在的键。"),
+    ("code.E0108.title", "应该只有一个值的地方出现了多行"),
+    ("code.E0108.explanation", "整个文件被当作一个数字来解析，但去掉首尾空白后仍然有多行。插入符指向第二行的第一个字符。"),
+    ("code.E0108.fix", "文件中只保留一个数字；包含多个数字的文件可以用 `learn numbers <path>` 检查。"),
+    ("code.E0200.title", "违反业务规则"),
+    ("code.E0200.explanation", "某个值违反了一条不属于本 crate 内置规则的规则。错误信息会说明是哪条规则以及期望的值。"),
+    ("code.E0200.fix", "修改这个值使其满足规则，或在 `codes::REGISTRY` 中为该规则分配单独的代码。"),
+    ("code.E0201.title", "数字必须是奇数"),
+    ("code.E0201.explanation", "`odd` 规则拒绝偶数。"),
+    ("code.E0201.fix", "改用奇数，或者删除 `odd` 规则。"),
+    ("code.E0202.title", "数字必须是偶数"),
+    ("code.E0202.explanation", "`even` 规则拒绝奇数。"),
+    ("code.E0202.fix", "改用偶数，或者删除 `even` 规则。"),
+    ("code.E0203.title", "数字超出范围"),
+    ("code.E0203.explanation", "`between A and B` 规则拒绝小于 A 或大于 B 的数字；两端都包含在内。"),
+    ("code.E0203.fix", "改用范围内的数字，或者放宽范围。"),
+    ("code.E0204.title", "数字不能整除"),
+    ("code.E0204.explanation", "`divisible by N` 规则拒绝除以 N 有余数的数字。"),
+    ("code.E0204.fix", "改用 N 的倍数。"),
+    ("code.E0205.title", "数字不被允许"),
+    ("code.E0205.explanation", "`not in a, b, ...` 规则拒绝列表中的每一个数字。"),
+    ("code.E0205.fix", "改用不在列表中的数字。"),
+    ("code.E0206.title", "检查未通过"),
+    ("code.E0206.explanation", "用 `bail!` 或 `ensure!` 写的检查失败了。它只带一条说明问题的消息，而不是结构化的规则违反。"),
+    ("code.E0206.fix", "阅读消息，修改它指出的输入。"),
+    ("code.E0301.title", "多个错误"),
+    ("code.E0301.explanation", "校验收集了所有问题，而不是在第一个问题处停下。每个错误都列在汇总下方，并带有各自的错误链。"),
+    ("code.E0301.fix", "逐一修复列出的错误；它们彼此独立。"),
+    ("code.E0302.title", "重试后放弃"),
+    ("code.E0302.explanation", "某个操作经过重试仍未成功：尝试次数用完、再等下去会超过截止时间，或者遇到了重试也无法解决的错误。每次尝试的错误都会列出。"),
+    ("code.E0302.fix", "查看最后一次尝试的错误。如果是临时性的，就允许更多次尝试或更晚的截止时间；否则修复其根本原因。"),
+    ("code.E0401.title", "代码发生了 panic"),
+    ("code.E0401.explanation", "某处调用了 `unwrap`、`expect`，越界访问了切片，或者调用了 `panic!`。这个 panic 被 `catch_unwind` 捕获并转换成了错误，因此其余部分得以继续运行。"),
+    ("code.E0401.fix", "panic 意味着 bug。找到报告的位置，返回 `Result` 或处理 `None`，而不是直接 panic。"),
+    ("code.E0402.title", "隔离运行的演示以错误状态退出"),
+    ("code.E0402.explanation", "使用 `--isolate` 时每个演示都在子进程中运行。这个演示以非零状态退出，可能是演示本身失败了 (它自己的错误打印在报告上方)，也可能是它调用了 `std::process::exit`。整个运行会把子进程的状态作为自己的退出码。"),
+    ("code.E0402.fix", "不加 `--isolate` 单独运行这个演示，查看它的错误并修复。"),
+    ("code.E0403.title", "隔离运行的演示被信号终止"),
+    ("code.E0403.explanation", "使用 `--isolate` 时每个演示都在子进程中运行。这个演示没有正常退出，而是被 SIGSEGV 或 SIGKILL 之类的信号终止。如果没有隔离，整个运行都会随之结束。"),
+    ("code.E0403.fix", "检查 unsafe 代码、栈溢出，或者是否有外部进程杀死了演示。"),
+    ("code.E0404.title", "隔离运行的演示异常中止"),
+    ("code.E0404.explanation", "使用 `--isolate` 时每个演示都在子进程中运行。这个演示异常中止了：它调用了 `std::process::abort`，在 panic 过程中再次 panic，或者以 `panic = \"abort\"` 构建。abort 不能像 panic 那样被捕获，所以如果没有隔离，整个运行都会随之结束。"),
+    ("code.E0404.fix", "在报告上方的演示 stderr 中找到导致中止的原因，改为返回错误。"),
+    ("panic.report", "程序发生了 panic，这是一个 bug\n  消息: {message}\n  位置: {location}\n  提示: 对调用者能够处理的失败返回 `Result`；设置 RUST_BACKTRACE=1 可以查看代码是如何执行到这里的"),
+    ("panic.unknown_location", "<未知位置>"),
+    ("panic.unknown_payload", "<不是字符串的 panic 负载>"),
+];
@@ -0,0 +1,138 @@
+use std::env;
+use std::io;
+use std::process::{Command, ExitStatus};
+
+use crate::category::Category;
+use crate::codes;
+use crate::exit;
+use crate::i18n::tf;
+use crate::json::{self, Value};
+use crate::result_demo::DemoError;
+
+// What `process::abort` raises
+const SIGABRT: i32 = 6;
+
+// How a child process that ran one demo ended, when it did not succeed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    // The demo failed, or called `process::exit` with a non-zero status
+    Exit(i32),
+    // Killed by a signal other than SIGABRT, e.g. SIGSEGV or SIGKILL
+    Signal(i32),
+    // `process::abort`, a panic while panicking, or anything else raising SIGABRT
+    Aborted,
+}
+
+impl Termination {
+    // `None` when the child succeeded
+    pub fn of(status: ExitStatus) -> Option<Termination> {
+        if status.success() {
+            return None;
+        }
+        match (status.code(), signal(status)) {
+            (Some(code), _) => Some(Termination::Exit(code)),
+            (None, Some(SIGABRT)) => Some(Termination::Aborted),
+            (None, Some(signal)) => Some(Termination::Signal(signal)),
+            (None, None) => Some(Termination::Exit(-1)),
+        }
+    }
+
+    // `reported` is the code of the error the demo itself failed with, if it said
+    pub fn describe(self, demo: &str, reported: Option<&str>) -> String {
+        let ended = match self {
+            Termination::Exit(code) => tf("isolate.exit", &[("demo", &demo), ("code", &code)]),
+            Termination::Signal(signal) => tf("isolate.signal", &[("demo", &demo), ("signal", &signal)]),
+            Termination::Aborted => tf("isolate.aborted", &[("demo", &demo)]),
+        };
+        match reported {
+            Some(code) => tf("isolate.reported", &[("ended", &ended), ("code", &code)]),
+            None => ended,
+        }
+    }
+
+    // Stable identifier for machine-readable output
+    pub fn id(self) -> &'static str {
+        match self {
+            Termination::Exit(_) => "exit",
+            Termination::Signal(_) => "signal",
+            Termination::Aborted => "aborted",
+        }
+    }
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Termination::Exit(_) => "E0402",
+            Termination::Signal(_) => "E0403",
+            Termination::Aborted => "E0404",
+        }
+    }
+
+    // A child's exit status is its own error's exit code, so it is passed on;
+    // a child that never got to exit is a bug
+    pub fn exit_code(self) -> u8 {
+        match self {
+            Termination::Exit(code) => u8::try_from(code).ok().filter(|&c| c != exit::SUCCESS).unwrap_or(exit::SOFTWARE),
+            Termination::Signal(_) | Termination::Aborted => exit::SOFTWARE,
+        }
+    }
+
+    // Read back from the exit code the child chose, as far as it tells
+    pub fn category(self) -> Category {
+        match self.exit_code() {
+            exit::BUSINESS_RULE => Category::Domain,
+            exit::USAGE | exit::DATA_ERR => Category::User,
+            exit::NO_INPUT | exit::IO_ERR | exit::NO_PERM => Category::Environment,
+            exit::TEMP_FAIL => Category::Transient,
+            _ => Category::Bug,
+        }
+    }
+}
+
+#[cfg(unix)]
+pub fn signal(status: ExitStatus) -> Option<i32> {
+    use std::os::unix::process::ExitStatusExt;
+    status.signal()
+}
+
+#[cfg(not(unix))]
+pub fn signal(_status: ExitStatus) -> Option<i32> {
+    None
+}
+
+// What a child printed and how it ended
+pub struct Finished {
+    // The JSON record the child wrote for its demo, unless it died first
+    pub record: Option<Value>,
+    // Whatever else the child wrote to stdout
+    pub stdout: String,
+    pub stderr: String,
+    pub status: ExitStatus,
+    pub result: Result<(), DemoError>,
+}
+
+// Run `<current executable> run <demo> --output json <args>...` and wait for it.
+// Only works from the `learn` binary itself, since that is what gets re-executed
+pub fn run(demo: &str, args: &[String]) -> io::Result<Finished> {
+    let output = Command::new(env::current_exe()?).args(["run", demo, "--output", "json"]).args(args).output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines: Vec<&str> = stdout.lines().collect();
+    // The record is the last thing the child prints
+    let at = lines.iter().rposition(|line| matches!(json::parse(line), Some(Value::Object(_))));
+    let record = at.map(|i| lines.remove(i)).and_then(json::parse);
+    let stdout = lines.iter().map(|line| format!("{}\n", line)).collect();
+    let result = match Termination::of(output.status) {
+        None => Ok(()),
+        Some(termination) => {
+            let reported = record.as_ref().and_then(reported_code);
+            Err(DemoError::Isolated { demo: demo.to_string(), termination, reported })
+        }
+    };
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    Ok(Finished { record, stdout, stderr, status: output.status, result })
+}
+
+// The code of the error the demo failed with, from the `error` in its record
+fn reported_code(record: &Value) -> Option<&'static str> {
+    let code = record.get("error")?.get("code")?.as_str()?;
+    codes::lookup(code).map(|entry| entry.code)
+}
@@ -0,0 +1,267 @@
+use std::fmt::{Display, Formatter, Write};
+
+// `s` as a quoted JSON string literal
+pub fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// A JSON document; `Display` writes it compactly on one line, as JSON Lines needs
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    // Non-finite numbers have no JSON form and are written as `null`
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    // Keys keep insertion order
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    pub fn object<'k>(fields: impl IntoIterator<Item = (&'k str, Value)>) -> Value {
+        Value::Object(fields.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    }
+
+    // This object with `key` set to `value`, replacing an earlier `key`; anything
+    // other than an object is returned unchanged
+    pub fn with(mut self, key: &str, value: Value) -> Value {
+        if let Value::Object(fields) = &mut self {
+            fields.retain(|(k, _)| k != key);
+            fields.push((key.to_string(), value));
+        }
+        self
+    }
+
+    // The first field named `key`, if this is an object
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(items) => Some(items),
+            _ => None,
+        }
+    }
+}
+
+// Read back one JSON document, such as a line `Display` wrote; `None` if `text`
+// is anything else, including a document followed by more than whitespace
+pub fn parse(text: &str) -> Option<Value> {
+    let mut parser = Parser { rest: text };
+    let value = parser.value()?;
+    parser.rest.trim_start().is_empty().then_some(value)
+}
+
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl Parser<'_> {
+    fn value(&mut self) -> Option<Value> {
+        self.rest = self.rest.trim_start();
+        match self.rest.chars().next()? {
+            'n' => self.literal("null", Value::Null),
+            't' => self.literal("true", Value::Bool(true)),
+            'f' => self.literal("false", Value::Bool(false)),
+            '"' => self.string().map(Value::String),
+            '[' => self.array(),
+            '{' => self.object(),
+            _ => self.number(),
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Value) -> Option<Value> {
+        self.rest = self.rest.strip_prefix(word)?;
+        Some(value)
+    }
+
+    // Takes `token` after any whitespace, if that is what comes next
+    fn eat(&mut self, token: char) -> bool {
+        match self.rest.trim_start().strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn number(&mut self) -> Option<Value> {
+        let end = self.rest.find(|c: char| !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E')).unwrap_or(self.rest.len());
+        let (number, rest) = self.rest.split_at(end);
+        // Rust also reads `inf`, `NaN` and `+1`, none of which get this far
+        if !number.starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+            return None;
+        }
+        self.rest = rest;
+        number.parse().ok().map(Value::Number)
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let mut chars = self.rest.strip_prefix('"')?.chars();
+        let mut s = String::new();
+        loop {
+            match chars.next()? {
+                '"' => break,
+                '\\' => {
+                    let c = match chars.next()? {
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'u' => {
+                            let unit = hex4(&mut chars)?;
+                            match unit {
+                                // A surrogate pair stands for one character beyond U+FFFF
+                                0xd800..=0xdbff => {
+                                    let (Some('\\'), Some('u')) = (chars.next(), chars.next()) else { return None };
+                                    let low = hex4(&mut chars).filter(|low| (0xdc00..=0xdfff).contains(low))?;
+                                    char::from_u32(0x10000 + ((unit - 0xd800) << 10) + (low - 0xdc00))?
+                                }
+                                unit => char::from_u32(unit)?,
+                            }
+                        }
+                        c @ ('"' | '\\' | '/') => c,
+                        _ => return None,
+                    };
+                    s.push(c);
+                }
+                c if (c as u32) < 0x20 => return None,
+                c => s.push(c),
+            }
+        }
+        self.rest = chars.as_str();
+        Some(s)
+    }
+
+    fn array(&mut self) -> Option<Value> {
+        self.rest = self.rest.strip_prefix('[')?;
+        let mut items = Vec::new();
+        if self.eat(']') {
+            return Some(Value::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            if self.eat(']') {
+                return Some(Value::Array(items));
+            }
+            if !self.eat(',') {
+                return None;
+            }
+        }
+    }
+
+    fn object(&mut self) -> Option<Value> {
+        self.rest = self.rest.strip_prefix('{')?;
+        let mut fields = Vec::new();
+        if self.eat('}') {
+            return Some(Value::Object(fields));
+        }
+        loop {
+            self.rest = self.rest.trim_start();
+            let key = self.string()?;
+            if !self.eat(':') {
+                return None;
+            }
+            fields.push((key, self.value()?));
+            if self.eat('}') {
+                return Some(Value::Object(fields));
+            }
+            if !self.eat(',') {
+                return None;
+            }
+        }
+    }
+}
+
+// Four hex digits after `\u`
+fn hex4(chars: &mut std::str::Chars<'_>) -> Option<u32> {
+    let digits: String = chars.by_ref().take(4).collect();
+    let valid = digits.len() == 4 && digits.chars().all(|c| c.is_ascii_hexdigit());
+    valid.then(|| u32::from_str_radix(&digits, 16).ok()).flatten()
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Bool(b) => write!(f, "{}", b),
+            Value::Number(n) if n.is_finite() => write!(f, "{}", n),
+            Value::Number(_) => f.write_str("null"),
+            Value::String(s) => f.write_str(&string(s)),
+            Value::Array(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            }
+            Value::Object(fields) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}:{}", string(key), value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self { Value::String(s.to_string()) }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self { Value::String(s) }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self { Value::Bool(b) }
+}
+
+impl From<u8> for Value {
+    fn from(n: u8) -> Self { Value::Number(f64::from(n)) }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Self { Value::Array(items.into_iter().map(Into::into).collect()) }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(value: Option<T>) -> Self { value.map_or(Value::Null, Into::into) }
+}
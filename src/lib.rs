@@ -0,0 +1,24 @@
+pub mod category;
+pub mod chain;
+pub mod cli;
+pub mod codes;
+pub mod context;
+pub mod demo;
+pub mod diagnostics;
+pub mod exit;
+pub mod fault;
+pub mod fs;
+pub mod i18n;
+pub mod isolate;
+pub mod json;
+mod macros;
+pub mod numbers;
+pub mod option_demo;
+pub mod panic_demo;
+pub mod recovery;
+pub mod report;
+pub mod result_demo;
+pub mod retry;
+pub mod rules;
+pub mod snapshot;
+pub mod validation;
@@ -0,0 +1,43 @@
+// Early returns for functions returning `DemoError`, `Box<dyn Error>` or `Report`.
+// A format string makes a `DemoError::Message`; any other argument is an error
+// that converts into the function's error type:
+//
+//   bail!(DemoError::BusinessRule(violation));
+//   bail!("{} is not allowed", n);
+#[macro_export]
+macro_rules! bail {
+    ($fmt:literal $(, $arg:expr)* $(,)?) => {
+        return ::std::result::Result::Err(::std::convert::From::from(
+            $crate::result_demo::DemoError::Message(::std::format!($fmt $(, $arg)*)),
+        ))
+    };
+    ($err:expr $(,)?) => {
+        return ::std::result::Result::Err(::std::convert::From::from($err))
+    };
+}
+
+// `bail!` unless `cond` holds:
+//
+//   ensure!(n % 2 != 0, DemoError::BusinessRule(odd_violation(n)));
+//   ensure!(n <= 100, "{} is too big", n);
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr, $($rest:tt)+) => {
+        if !$cond {
+            $crate::bail!($($rest)+);
+        }
+    };
+}
+
+// `ResultExt::with_context` without the closure; the message is only built on error:
+//
+//   let text = context!(fs.read_to_string(path), "reading {}", path.display())?;
+#[macro_export]
+macro_rules! context {
+    ($result:expr, $fmt:literal $(, $arg:expr)* $(,)?) => {
+        $crate::context::ResultExt::with_context($result, || ::std::format!($fmt $(, $arg)*))
+    };
+    ($result:expr, $context:expr $(,)?) => {
+        $crate::context::ResultExt::with_context($result, || $context)
+    };
+}
@@ -1,7 +1,5 @@
-mod result_demo;
+use learn::exit::Outcome;
 
-fn main() {
-    if let Err(e) = result_demo::run() {
-        eprintln!("demo 运行错误: {}", e);
-    }
+fn main() -> Outcome {
+    learn::cli::main(std::env::args().skip(1))
 }
@@ -0,0 +1,108 @@
+use std::io::{BufRead, BufReader, Lines, Read};
+use std::path::{Path, PathBuf};
+
+use crate::context::ResultExt;
+use crate::diagnostics::Diagnostic;
+use crate::fs::FileSystem;
+use crate::i18n::tf;
+use crate::result_demo::DemoError;
+
+// Reads one number per line, skipping blank lines and `#` comments.
+// Yields `(line_no, value)` or the error for that line; an IO error ends the stream
+pub struct NumberReader<R> {
+    path: PathBuf,
+    lines: Lines<R>,
+    line_no: usize,
+    failed_io: bool,
+}
+
+impl NumberReader<BufReader<Box<dyn Read>>> {
+    pub fn open(fs: &dyn FileSystem, path: &Path) -> Result<Self, DemoError> {
+        let file = fs.open(path).with_context(|| tf("context.open_numbers", &[("path", &path.display())]))?;
+        Ok(NumberReader::new(path, BufReader::new(file)))
+    }
+}
+
+impl<R: BufRead> NumberReader<R> {
+    // `path` is only used to label diagnostics
+    pub fn new(path: &Path, reader: R) -> Self {
+        NumberReader { path: path.to_path_buf(), lines: reader.lines(), line_no: 0, failed_io: false }
+    }
+}
+
+impl<R: BufRead> Iterator for NumberReader<R> {
+    type Item = Result<(usize, u32), DemoError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed_io {
+            return None;
+        }
+        loop {
+            self.line_no += 1;
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => {
+                    self.failed_io = true;
+                    let path = self.path.display();
+                    return Some(Err(e).with_context(|| tf("context.read_numbers", &[("path", &path)])));
+                }
+            };
+            let token = line.split('#').next().unwrap_or("").trim();
+            if token.is_empty() {
+                continue;
+            }
+            return Some(match token.parse() {
+                Ok(n) => Ok((self.line_no, n)),
+                Err(e) => {
+                    let diag = Diagnostic::for_line::<u32>(&self.path, self.line_no, &line, token, e);
+                    Err(DemoError::Diagnostic(Box::new(diag)))
+                }
+            });
+        }
+    }
+}
+
+// What to do when a line cannot be read as a number
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorMode {
+    // Return the first error and stop reading
+    FailFast,
+    // Record the error in the summary and carry on with the next line
+    CollectAll,
+}
+
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub count: usize,
+    pub sum: u64,
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+    // Lines that failed, in file order; always empty under `FailFast`
+    pub errors: Vec<DemoError>,
+}
+
+impl Summary {
+    fn add(&mut self, n: u32) {
+        self.count += 1;
+        self.sum += u64::from(n);
+        self.min = Some(self.min.map_or(n, |m| m.min(n)));
+        self.max = Some(self.max.map_or(n, |m| m.max(n)));
+    }
+}
+
+// Fold a stream of numbers into a summary. IO errors always end the
+// summary, since nothing after them can be trusted
+pub fn summarize<I>(numbers: I, mode: ErrorMode) -> Result<Summary, DemoError>
+where
+    I: IntoIterator<Item = Result<(usize, u32), DemoError>>,
+{
+    let mut summary = Summary::default();
+    for item in numbers {
+        match item {
+            Ok((_, n)) => summary.add(n),
+            Err(e @ DemoError::Diagnostic(_)) if mode == ErrorMode::CollectAll => summary.errors.push(e),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(summary)
+}
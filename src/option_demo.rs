@@ -0,0 +1,215 @@
+use std::error::Error;
+use std::io::{self, Write};
+use std::num::ParseIntError;
+
+use crate::demo::{Context, Demo, Registry};
+use crate::i18n::t;
+use crate::result_demo::DemoError;
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Basics);
+    registry.register(Combinators);
+    registry.register(Ownership);
+    registry.register(Conversions);
+    registry.register(NumberLookup);
+}
+
+struct Basics;
+
+impl Demo for Basics {
+    fn name(&self) -> &'static str { "option-basics" }
+    fn title(&self) -> &'static str { t("demo.option_basics.title") }
+    fn description(&self) -> &'static str { t("demo.option_basics.description") }
+    fn tags(&self) -> &'static [&'static str] { &["option"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> { Ok(basics(&mut ctx.out)?) }
+}
+
+struct Combinators;
+
+impl Demo for Combinators {
+    fn name(&self) -> &'static str { "option-combinators" }
+    fn title(&self) -> &'static str { t("demo.option_combinators.title") }
+    fn description(&self) -> &'static str { t("demo.option_combinators.description") }
+    fn tags(&self) -> &'static [&'static str] { &["option"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> { Ok(combinators(&mut ctx.out)?) }
+}
+
+struct Ownership;
+
+impl Demo for Ownership {
+    fn name(&self) -> &'static str { "option-take" }
+    fn title(&self) -> &'static str { t("demo.option_take.title") }
+    fn description(&self) -> &'static str { t("demo.option_take.description") }
+    fn tags(&self) -> &'static [&'static str] { &["option"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> { Ok(take_and_replace(&mut ctx.out)?) }
+}
+
+struct Conversions;
+
+impl Demo for Conversions {
+    fn name(&self) -> &'static str { "option-result" }
+    fn title(&self) -> &'static str { t("demo.option_result.title") }
+    fn description(&self) -> &'static str { t("demo.option_result.description") }
+    fn tags(&self) -> &'static [&'static str] { &["option", "result"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> { Ok(conversions(&mut ctx.out)?) }
+}
+
+struct NumberLookup;
+
+impl Demo for NumberLookup {
+    fn name(&self) -> &'static str { "number-lookup" }
+    fn title(&self) -> &'static str { t("demo.number_lookup.title") }
+    fn description(&self) -> &'static str { t("demo.number_lookup.description") }
+    fn tags(&self) -> &'static [&'static str] { &["option", "result", "custom-error"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        for key in ["seven", "lucky", "ten"] {
+            match lookup(TABLE, key) {
+                Ok(n) => writeln!(ctx.out, "lookup({:?}) => Ok({})", key, n)?,
+                Err(e) => writeln!(ctx.out, "lookup({:?}) => [{}] {}", key, e.code(), e)?,
+            }
+        }
+
+        // An `Option` can only say that there is no number, not why
+        writeln!(ctx.out, "\nlookup_option(\"lucky\") => {:?}", lookup_option(TABLE, "lucky"))?;
+        writeln!(ctx.out, "lookup_option(\"ten\") => {:?}", lookup_option(TABLE, "ten"))?;
+
+        // For an optional key only a bad value is an error
+        for key in ["seven", "lucky", "ten"] {
+            match lookup_optional(TABLE, key) {
+                Ok(n) => writeln!(ctx.out, "lookup_optional({:?}) => Ok({:?})", key, n)?,
+                Err(e) => writeln!(ctx.out, "lookup_optional({:?}) => [{}] {}", key, e.code(), e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn basics(out: &mut dyn Write) -> io::Result<()> {
+    let numbers = [42, 7];
+    let some_value: Option<i32> = numbers.first().copied();
+    let no_value: Option<i32> = numbers.get(5).copied();
+
+    // Inspect options
+    writeln!(out, "some_value.is_some() = {}", some_value.is_some())?;
+    writeln!(out, "no_value.is_none() = {}", no_value.is_none())?;
+
+    // Unwrap with default
+    writeln!(out, "unwrap_or: {}", no_value.unwrap_or(-1))?;
+    writeln!(out, "unwrap_or_default: {}", no_value.unwrap_or_default())?;
+
+    // Match
+    match some_value {
+        Some(v) => writeln!(out, "match Some: {}", v)?,
+        None => writeln!(out, "match None")?,
+    }
+
+    // ? returns None early from a function that returns Option
+    writeln!(out, "first_even_doubled([3, 4, 5]) = {:?}", first_even_doubled(&[3, 4, 5]))?;
+    writeln!(out, "first_even_doubled([3, 5]) = {:?}", first_even_doubled(&[3, 5]))?;
+    Ok(())
+}
+
+fn first_even_doubled(values: &[i32]) -> Option<i32> {
+    let even = values.iter().find(|v| *v % 2 == 0)?;
+    even.checked_mul(2)
+}
+
+fn combinators(out: &mut dyn Write) -> io::Result<()> {
+    let width: Option<u32> = Some(3);
+    let height: Option<u32> = Some(4);
+
+    // map: transform the value if there is one
+    writeln!(out, "map squared = {:?}", width.map(|w| w * w))?;
+
+    // filter: keep the value only if it passes a test
+    writeln!(out, "filter even = {:?}", width.filter(|w| w % 2 == 0))?;
+    writeln!(out, "filter odd = {:?}", width.filter(|w| w % 2 == 1))?;
+
+    // zip: a pair when both are there, otherwise nothing
+    writeln!(out, "zip = {:?}", width.zip(height))?;
+    writeln!(out, "zip with None = {:?}", width.zip(None::<u32>))?;
+    writeln!(out, "area = {:?}", width.zip(height).map(|(w, h)| w * h))?;
+
+    // and_then: chain computations that may also produce nothing
+    writeln!(out, "and_then checked_sub(5) = {:?}", width.and_then(|w| w.checked_sub(5)))?;
+
+    // or: fall back to another option
+    writeln!(out, "None.or(height) = {:?}", None.or(height))?;
+    Ok(())
+}
+
+fn take_and_replace(out: &mut dyn Write) -> io::Result<()> {
+    // take: move the value out and leave None behind
+    let mut slot = Some(String::from("first"));
+    let taken = slot.take();
+    writeln!(out, "take => taken = {:?}, slot = {:?}", taken, slot)?;
+
+    // replace: put a new value in and get the old one back
+    let old = slot.replace(String::from("second"));
+    writeln!(out, "replace => old = {:?}, slot = {:?}", old, slot)?;
+    let old = slot.replace(String::from("third"));
+    writeln!(out, "replace => old = {:?}, slot = {:?}", old, slot)?;
+
+    // get_or_insert: fill an empty slot, keep a full one
+    let mut cache: Option<u64> = None;
+    let first = *cache.get_or_insert(42);
+    let second = *cache.get_or_insert(0);
+    writeln!(out, "get_or_insert => {}, then {}", first, second)?;
+
+    // take in a loop: a one-shot value is handed out exactly once
+    let mut greeting = Some("welcome");
+    for visit in 1..=2 {
+        writeln!(out, "visit {} => {:?}", visit, greeting.take())?;
+    }
+    Ok(())
+}
+
+fn conversions(out: &mut dyn Write) -> io::Result<()> {
+    // ok_or: None becomes the given error
+    let missing: Option<i32> = None;
+    writeln!(out, "ok_or => {:?}", missing.ok_or("nothing here"))?;
+
+    // ok_or_else: the error is only built when it is needed
+    writeln!(out, "ok_or_else => {:?}", Some(7).ok_or_else(|| "never built".to_string()))?;
+
+    // ok / err: keep one side of a Result, dropping the other
+    let parsed = "12".parse::<i32>();
+    let failed = "x".parse::<i32>();
+    writeln!(out, "ok() => {:?}, {:?}", parsed.clone().ok(), failed.clone().ok())?;
+    writeln!(out, "err() => {:?}, {:?}", parsed.err(), failed.err())?;
+
+    // transpose: an optional value that may fail to parse becomes a
+    // Result that is only an error when there was a bad value
+    for input in [Some("5"), Some("five"), None] {
+        let value: Result<Option<i32>, ParseIntError> = input.map(str::parse).transpose();
+        writeln!(out, "transpose {:?} => {:?}", input, value)?;
+    }
+
+    // ... and back again
+    let back: Option<Result<i32, ParseIntError>> = Ok(Some(5)).transpose();
+    writeln!(out, "transpose Ok(Some(5)) => {:?}", back)?;
+    Ok(())
+}
+
+// A settings-like table of numbers, one of them mistyped
+const TABLE: &[(&str, &str)] = &[("one", "1"), ("seven", "7"), ("lucky", "7x")];
+
+fn find<'a>(table: &[(&str, &'a str)], key: &str) -> Option<&'a str> {
+    table.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+}
+
+// A missing key is an error that names the key
+fn lookup(table: &[(&str, &str)], key: &str) -> Result<i64, DemoError> {
+    let raw = find(table, key).ok_or_else(|| DemoError::MissingKey(key.to_string()))?;
+    Ok(raw.parse()?)
+}
+
+// `?` works on `Option` too, but a bad value and a missing key both end up as `None`
+fn lookup_option(table: &[(&str, &str)], key: &str) -> Option<i64> {
+    let raw = find(table, key)?;
+    raw.parse().ok()
+}
+
+fn lookup_optional(table: &[(&str, &str)], key: &str) -> Result<Option<i64>, DemoError> {
+    Ok(find(table, key).map(str::parse).transpose()?)
+}
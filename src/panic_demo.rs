@@ -0,0 +1,128 @@
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cell::{Cell, RefCell};
+use std::error::Error;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::Once;
+
+use crate::demo::{Context, Demo, Registry};
+use crate::i18n::{t, tf};
+use crate::result_demo::DemoError;
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Panics);
+}
+
+thread_local! {
+    // How many `catch` calls are running on this thread; their panics are recorded, not printed
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    // Where the last caught panic happened, which only the hook gets to see
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+static RECORDING: Once = Once::new();
+
+// Print `report` for every panic nobody catches, in the current language,
+// followed by a backtrace when RUST_BACKTRACE asks for one
+pub fn install_hook() {
+    panic::set_hook(Box::new(|info| {
+        if !record(info) {
+            eprintln!("{}", report(&message(info.payload()), &location(info)));
+            let backtrace = Backtrace::capture();
+            if backtrace.status() == BacktraceStatus::Captured {
+                eprintln!("\n{}\n{}", t("report.backtrace"), backtrace);
+            }
+        }
+    }));
+}
+
+// What the hook prints:
+//
+//   the program panicked, which is a bug
+//     message:  called `Option::unwrap()` on a `None` value
+//     location: src/main.rs:4:21
+//     hint:     ...
+pub fn report(message: &str, location: &str) -> String {
+    tf("panic.report", &[("message", &message), ("location", &location)])
+}
+
+// Run `f`, turning a panic into `DemoError::Panicked`. The closure is assumed
+// unwind safe: a demo that panics halfway leaves nothing behind that others use
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, DemoError> {
+    // Whatever hook is installed, panics under `catch` must be recorded instead of printed
+    RECORDING.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !record(info) {
+                previous(info);
+            }
+        }));
+    });
+    LOCATION.set(None);
+    CATCHING.set(CATCHING.get() + 1);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(CATCHING.get() - 1);
+    result.map_err(|payload| DemoError::Panicked {
+        message: message(payload.as_ref()),
+        location: LOCATION.take().unwrap_or_else(|| t("panic.unknown_location").to_string()),
+    })
+}
+
+// Remember where a panic under `catch` happened; false when nothing is catching it
+fn record(info: &PanicHookInfo<'_>) -> bool {
+    if CATCHING.get() == 0 {
+        return false;
+    }
+    LOCATION.set(Some(location(info)));
+    true
+}
+
+fn location(info: &PanicHookInfo<'_>) -> String {
+    info.location().map_or_else(|| t("panic.unknown_location").to_string(), |l| l.to_string())
+}
+
+// `panic!` payloads are a `&str` for literal messages and a `String` for formatted ones
+fn message(payload: &(dyn Any + Send)) -> String {
+    match payload.downcast_ref::<&str>() {
+        Some(s) => s.to_string(),
+        None => payload.downcast_ref::<String>().cloned().unwrap_or_else(|| t("panic.unknown_payload").to_string()),
+    }
+}
+
+struct Panics;
+
+impl Demo for Panics {
+    fn name(&self) -> &'static str { "panics" }
+    fn title(&self) -> &'static str { t("demo.panics.title") }
+    fn description(&self) -> &'static str { t("demo.panics.description") }
+    fn tags(&self) -> &'static [&'static str] { &["panic", "option", "result"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> { Ok(panics(&mut ctx.out)?) }
+}
+
+fn panics(out: &mut dyn Write) -> io::Result<()> {
+    let empty: Vec<i32> = Vec::new();
+
+    // unwrap / expect panic on None and Err
+    show(out, "unwrap on None", catch(|| empty.first().copied().unwrap()))?;
+    show(out, "expect on Err", catch(|| "x".parse::<i32>().expect("the port must be a number")))?;
+
+    // ... where unwrap_or and ? hand the decision to someone else
+    show(out, "unwrap_or on None", catch(|| empty.first().copied().unwrap_or(-1)))?;
+    show(out, "? on Err", catch(|| "x".parse::<i32>()).and_then(|r| Ok(r?)))?;
+
+    // Indexing past the end and explicit panic! work the same way
+    show(out, "index out of bounds", catch(|| empty[3]))?;
+    show(out, "panic! with format", catch(|| -> i32 { panic!("port {} is out of range", 70000) }))?;
+
+    // What the hook prints for a panic nobody catches
+    writeln!(out, "\n{}", report("called `Option::unwrap()` on a `None` value", "src/main.rs:4:21"))?;
+    Ok(())
+}
+
+fn show(out: &mut dyn Write, label: &str, result: Result<i32, DemoError>) -> io::Result<()> {
+    match result {
+        Ok(v) => writeln!(out, "{:<19} => Ok({})", label, v),
+        Err(e) => writeln!(out, "{:<19} => [{}] {}", label, e.code(), e),
+    }
+}
@@ -0,0 +1,71 @@
+use std::error::Error;
+use std::io;
+use std::num::ParseIntError;
+
+use crate::category::Category;
+use crate::chain;
+use crate::result_demo::DemoError;
+
+// An error in a chain whose type we know how to react to
+#[derive(Debug, Clone, Copy)]
+pub enum Known<'a> {
+    Io(&'a io::Error),
+    ParseInt(&'a ParseIntError),
+    Demo(&'a DemoError),
+}
+
+impl<'a> Known<'a> {
+    pub fn of(err: &'a (dyn Error + 'static)) -> Option<Known<'a>> {
+        if let Some(e) = err.downcast_ref::<io::Error>() {
+            return Some(Known::Io(e));
+        }
+        if let Some(e) = err.downcast_ref::<ParseIntError>() {
+            return Some(Known::ParseInt(e));
+        }
+        err.downcast_ref::<DemoError>().map(Known::Demo)
+    }
+
+    // Short type name, for messages
+    pub fn type_name(self) -> &'static str {
+        match self {
+            Known::Io(_) => "io::Error",
+            Known::ParseInt(_) => "ParseIntError",
+            Known::Demo(_) => "DemoError",
+        }
+    }
+}
+
+// Every error in `err`'s source chain that has a known type, outermost first
+pub fn known<'a>(err: &'a (dyn Error + 'static)) -> impl Iterator<Item = Known<'a>> {
+    chain::causes(err).filter_map(Known::of)
+}
+
+// What a caller can do about a failed read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    // The file is missing: write one with a default value
+    CreateDefault,
+    // The contents are wrong: ask for a corrected value
+    AskAgain,
+    // The failure is temporary: try the same thing again
+    Retry,
+    GiveUp,
+}
+
+impl Recovery {
+    // Decided by the first known error in the chain that says something;
+    // wrappers such as `DemoError::Context` defer to what they wrap
+    pub fn for_error(err: &(dyn Error + 'static)) -> Recovery {
+        for cause in known(err) {
+            match cause {
+                Known::Io(e) if e.kind() == io::ErrorKind::NotFound => return Recovery::CreateDefault,
+                Known::Io(e) if Category::of_io(e).is_transient() => return Recovery::Retry,
+                Known::Io(_) => return Recovery::GiveUp,
+                Known::ParseInt(_) => return Recovery::AskAgain,
+                Known::Demo(DemoError::Diagnostic(_) | DemoError::BusinessRule(_)) => return Recovery::AskAgain,
+                Known::Demo(_) => {}
+            }
+        }
+        Recovery::GiveUp
+    }
+}
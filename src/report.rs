@@ -0,0 +1,117 @@
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+use crate::chain;
+use crate::i18n::t;
+
+// A type-erased error like `Box<dyn Error>`, but one pointer wide, `Send + Sync`,
+// with a backtrace from where it was created and context layered on top.
+// Like `anyhow::Error` it does not implement `Error` itself, which is what lets
+// `?` convert any error into it
+pub struct Report(Box<Inner>);
+
+// Fails to compile if `Report` ever stops being safe to send across threads
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync + 'static>() {}
+    assert_send_sync::<Report>();
+};
+
+struct Inner {
+    error: Box<dyn Error + Send + Sync + 'static>,
+    // Only captured when `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` asks for it
+    backtrace: Backtrace,
+    // Innermost first, as they were attached
+    context: Vec<String>,
+}
+
+impl Report {
+    pub fn new<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        Report(Box::new(Inner { error: Box::new(error), backtrace: Backtrace::capture(), context: Vec::new() }))
+    }
+
+    pub fn context<C: Display>(mut self, context: C) -> Self {
+        self.0.context.push(context.to_string());
+        self
+    }
+
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.0.backtrace
+    }
+
+    // The error the report was created from, under all the context
+    pub fn root(&self) -> &(dyn Error + 'static) {
+        &*self.0.error
+    }
+
+    // Every message from the outermost context down to the last `source()`
+    pub fn chain(&self) -> impl Iterator<Item = String> + '_ {
+        let context = self.0.context.iter().rev().cloned();
+        context.chain(chain::shown(self.root()).map(|e| e.to_string()))
+    }
+
+    // The first error of type `E` anywhere in the source chain, so an
+    // `io::Error` is found even when wrapped in a `DemoError::Context`
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        chain::find(self.root())
+    }
+
+    // Take back the root error if it is an `E`; the context is dropped
+    pub fn downcast<E: Error + 'static>(self) -> Result<E, Report> {
+        let Inner { error, backtrace, context } = *self.0;
+        match error.downcast::<E>() {
+            Ok(e) => Ok(*e),
+            Err(error) => Err(Report(Box::new(Inner { error, backtrace, context }))),
+        }
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> From<E> for Report {
+    fn from(error: E) -> Self {
+        Report::new(error)
+    }
+}
+
+// `{}` shows the outermost message, `{:#}` the whole chain on one line
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            write!(f, "{}", self.chain().collect::<Vec<_>>().join(": "))
+        } else {
+            write!(f, "{}", self.chain().next().unwrap_or_default())
+        }
+    }
+}
+
+// What `fn main() -> Result<(), Report>` prints: the chain, then the backtrace if one was captured
+impl Debug for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (depth, message) in self.chain().enumerate() {
+            match depth {
+                0 => write!(f, "{}", message)?,
+                _ => write!(f, "\n{}{}: {}", "  ".repeat(depth), t("chain.caused_by"), message)?,
+            }
+        }
+        if self.0.backtrace.status() == BacktraceStatus::Captured {
+            write!(f, "\n\n{}\n{}", t("report.backtrace"), self.0.backtrace)?;
+        }
+        Ok(())
+    }
+}
+
+// Adds context to a `Result` whose error can become a `Report`, including one that already is
+pub trait WrapErr<T> {
+    fn wrap_err<C: Display>(self, context: C) -> Result<T, Report>;
+
+    fn wrap_err_with<C: Display, F: FnOnce() -> C>(self, f: F) -> Result<T, Report>;
+}
+
+impl<T, E: Into<Report>> WrapErr<T> for Result<T, E> {
+    fn wrap_err<C: Display>(self, context: C) -> Result<T, Report> {
+        self.map_err(|e| e.into().context(context))
+    }
+
+    fn wrap_err_with<C: Display, F: FnOnce() -> C>(self, f: F) -> Result<T, Report> {
+        self.map_err(|e| e.into().context(f()))
+    }
+}
@@ -1,148 +1,872 @@
 use std::error::Error;
 use std::fmt::{Display, Formatter};
-use std::fs::File;
-use std::io::{self, Read};
-use std::num::ParseIntError;
+use std::io::{self, BufRead, Read, Write};
+use std::net::Ipv4Addr;
+use std::num::{ParseFloatError, ParseIntError};
+use std::path::Path;
+use std::time::Duration;
+use std::str::{FromStr, ParseBoolError};
+
+use learn_derive::DemoErrorLike;
+
+use crate::category::Category;
+use crate::chain;
+use crate::codes;
+use crate::context::ResultExt;
+use crate::demo::{Context, Demo, Registry};
+use crate::diagnostics::{self, Diagnostic};
+use crate::fs::{FileSystem, MemoryFs};
+use crate::i18n::{t, tf};
+use crate::isolate::Termination;
+use crate::fault::{Fault, FaultyFs, Injection, Plan, Site};
+use crate::recovery::{self, Known, Recovery};
+use crate::report::{Report, WrapErr};
+use crate::retry::{Backoff, GiveUp, ManualClock, RetryPolicy};
+use crate::rules::{Constraint, RuleSet, RuleViolation};
+use crate::validation::Validated;
+use crate::{bail, context, ensure};
+
+// A small custom error to show how to define and use your own error types;
+// `DemoErrorLike` writes its `Display`, `Error` and `From<io::Error>` impls
+#[derive(Debug, DemoErrorLike)]
+pub enum DemoError {
+    #[error("{}: {0}", t("error.io"))]
+    Io(#[from] io::Error),
+    // Any `FromStr` failure; `expected` names the type that was being parsed, when known
+    #[error("{}: {source}", parse_heading(*.expected))]
+    Parse { expected: Option<&'static str>, #[source] source: Box<dyn Error + Send + Sync> },
+    // A lookup by key that found nothing
+    #[error("{}", tf("error.missing_key", &[("key", .0)]))]
+    MissingKey(String),
+    #[error("{}: {0}", t("error.business"))]
+    BusinessRule(RuleViolation),
+    // A failed check with only a message, from `bail!` or `ensure!`
+    #[error("{0}")]
+    Message(String),
+    // A parse failure located at a line and column of an input file
+    #[error(transparent)]
+    Diagnostic(Box<Diagnostic>),
+    // A human-readable step layered over the error that caused it
+    #[error("{context}")]
+    Context { context: String, #[source] source: Box<DemoError> },
+    // Several independent failures reported together; never empty
+    #[error("{}", tf("error.multiple", &[("count", &.0.len())]))]
+    Multiple(Vec<DemoError>),
+    // A panic caught by `panic_demo::catch`; `location` is `file:line:column`
+    #[error("{}", tf("error.panicked", &[("message", .message), ("location", .location)]))]
+    Panicked { message: String, location: String },
+    // A demo run in a child process of its own that did not exit successfully;
+    // `reported` is the code of the demo's own error, when the child printed one
+    #[error("{}", .termination.describe(.demo, *.reported))]
+    Isolated { demo: String, termination: Termination, reported: Option<&'static str> },
+    // A retried operation that never succeeded; one error per attempt, oldest first
+    #[error("{}", tf(.reason.message_key(), &[("count", &.attempts.len())]))]
+    RetriesExhausted { attempts: Vec<DemoError>, reason: GiveUp },
+}
+
+impl DemoError {
+    // Wrap the error from parsing a `T`
+    pub fn parse<T>(err: T::Err) -> Self
+    where
+        T: FromStr,
+        T::Err: Error + Send + Sync + 'static,
+    {
+        DemoError::Parse { expected: Some(diagnostics::type_label::<T>()), source: Box::new(err) }
+    }
+
+    // A single error stays as it is, several become `Multiple`; no errors is no error
+    pub fn from_errors(mut errors: Vec<DemoError>) -> Option<Self> {
+        match errors.len() {
+            0 => None,
+            1 => Some(errors.remove(0)),
+            _ => Some(DemoError::Multiple(errors)),
+        }
+    }
+
+    // Name of the variant, stable across languages for machine-readable output
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            DemoError::Io(_) => "Io",
+            DemoError::Parse { .. } => "Parse",
+            DemoError::MissingKey(_) => "MissingKey",
+            DemoError::BusinessRule(_) => "BusinessRule",
+            DemoError::Message(_) => "Message",
+            DemoError::Diagnostic(_) => "Diagnostic",
+            DemoError::Context { .. } => "Context",
+            DemoError::Multiple(_) => "Multiple",
+            DemoError::Panicked { .. } => "Panicked",
+            DemoError::Isolated { .. } => "Isolated",
+            DemoError::RetriesExhausted { .. } => "RetriesExhausted",
+        }
+    }
+
+    // Who has to act for this error to go away; wrappers defer to what they wrap
+    pub fn category(&self) -> Category {
+        match self {
+            DemoError::Io(e) => Category::of_io(e),
+            DemoError::Parse { .. } | DemoError::MissingKey(_) | DemoError::Diagnostic(_) => Category::User,
+            DemoError::BusinessRule(_) | DemoError::Message(_) => Category::Domain,
+            DemoError::Context { source, .. } => source.category(),
+            DemoError::Multiple(errors) => errors.first().map_or(Category::Bug, DemoError::category),
+            DemoError::Panicked { .. } => Category::Bug,
+            DemoError::Isolated { termination, .. } => termination.category(),
+            DemoError::RetriesExhausted { attempts, .. } => attempts.last().map_or(Category::Bug, DemoError::category),
+        }
+    }
+
+    // Stable code from `codes::REGISTRY`; wrappers defer to what they wrap
+    pub fn code(&self) -> &'static str {
+        match self {
+            DemoError::Io(e) => codes::for_io(e),
+            DemoError::Parse { source, .. } => codes::for_parse(source.as_ref()),
+            DemoError::MissingKey(_) => "E0107",
+            DemoError::BusinessRule(v) => v.code(),
+            DemoError::Message(_) => "E0206",
+            DemoError::Diagnostic(d) => d.code(),
+            DemoError::Context { source, .. } => source.code(),
+            DemoError::Multiple(_) => "E0301",
+            DemoError::Panicked { .. } => "E0401",
+            DemoError::Isolated { termination, .. } => termination.code(),
+            DemoError::RetriesExhausted { .. } => "E0302",
+        }
+    }
+
+    // Whether trying the same thing again may succeed
+    pub fn is_transient(&self) -> bool {
+        self.category().is_transient()
+    }
+
+    // Whether the message already ends with the `source()`'s, so showing
+    // the source again on a line of its own would repeat it
+    pub fn embeds_source(&self) -> bool {
+        matches!(self, DemoError::Io(_) | DemoError::Parse { .. })
+    }
+
+    // Errors grouped under this one that are not its `source()`
+    pub fn related(&self) -> &[DemoError] {
+        match self {
+            DemoError::Multiple(errors) => errors,
+            DemoError::RetriesExhausted { attempts, .. } => attempts,
+            _ => &[],
+        }
+    }
+}
+
+// `?` on a parse error cannot tell which integer or float type was wanted;
+// use `DemoError::parse::<T>` where it matters
+impl From<ParseIntError> for DemoError {
+    fn from(err: ParseIntError) -> Self { DemoError::Parse { expected: None, source: Box::new(err) } }
+}
+
+impl From<ParseFloatError> for DemoError {
+    fn from(err: ParseFloatError) -> Self { DemoError::Parse { expected: None, source: Box::new(err) } }
+}
+
+// Only `bool` fails with this one
+impl From<ParseBoolError> for DemoError {
+    fn from(err: ParseBoolError) -> Self { DemoError::parse::<bool>(err) }
+}
+
+fn parse_heading(expected: Option<&str>) -> String {
+    match expected {
+        Some(ty) => tf("error.parse_expected", &[("type", &ty)]),
+        None => t("error.parse").to_string(),
+    }
+}
+
+pub fn register(registry: &mut Registry) {
+    registry.register(Basics);
+    registry.register(QuestionMark);
+    registry.register(EarlyReturn);
+    registry.register(Combinators);
+    registry.register(CustomError);
+    registry.register(BoxedError);
+    registry.register(Reports);
+    registry.register(Recover);
+    registry.register(ErrorChain);
+    registry.register(Diagnostics);
+    registry.register(GenericValues);
+    registry.register(VirtualFs);
+    registry.register(Retry);
+    registry.register(BusinessRules);
+    registry.register(RulesEngine);
+    registry.register(Validation);
+}
+
+struct Basics;
+
+impl Demo for Basics {
+    fn name(&self) -> &'static str { "basics" }
+    fn title(&self) -> &'static str { t("demo.basics.title") }
+    fn description(&self) -> &'static str { t("demo.basics.description") }
+    fn tags(&self) -> &'static [&'static str] { &["result"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> { basics(&mut ctx.out) }
+}
+
+struct QuestionMark;
+
+impl Demo for QuestionMark {
+    fn name(&self) -> &'static str { "question-mark" }
+    fn title(&self) -> &'static str { t("demo.question_mark.title") }
+    fn description(&self) -> &'static str { t("demo.question_mark.description") }
+    fn tags(&self) -> &'static [&'static str] { &["result", "io", "custom-error"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        Ok(demonstrate_question_mark_operator(&mut ctx.out, ctx.fs.as_ref(), &ctx.source_path)?)
+    }
+}
+
+struct EarlyReturn;
+
+impl Demo for EarlyReturn {
+    fn name(&self) -> &'static str { "early-return" }
+    fn title(&self) -> &'static str { t("demo.early_return.title") }
+    fn description(&self) -> &'static str { t("demo.early_return.description") }
+    fn tags(&self) -> &'static [&'static str] { &["result", "macros", "custom-error"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        let fs = MemoryFs::new().file("7.txt", "7\n").file("8.txt", "8\n").file("13.txt", "13\n").file("101.txt", "101\n");
+        for (i, name) in ["7.txt", "8.txt", "13.txt", "101.txt", "missing.txt"].into_iter().enumerate() {
+            let path = Path::new(name);
+            writeln!(ctx.out, "{}{}:", if i > 0 { "\n" } else { "" }, name)?;
+            show(&mut ctx.out, "before", checked_before(&fs, path))?;
+            show(&mut ctx.out, "after", checked_after(&fs, path))?;
+            match checked_boxed(&fs, path) {
+                Ok(n) => writeln!(ctx.out, "{:<9} => Ok({})", "boxed", n)?,
+                Err(e) => writeln!(ctx.out, "{:<9} => {}", "boxed", chain::plain(e.as_ref()))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// The checks spelled out by hand, one `return Err(...)` each
+fn checked_before(fs: &dyn FileSystem, path: &Path) -> Result<i32, DemoError> {
+    let text = match fs.read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            let context = tf("demo.early_return.reading", &[("path", &path.display())]);
+            return Err(DemoError::Context { context, source: Box::new(e.into()) });
+        }
+    };
+    let n: i32 = text.trim().parse()?;
+    if n % 2 == 0 {
+        return Err(DemoError::BusinessRule(odd_violation(n)));
+    }
+    if n > 100 {
+        return Err(DemoError::Message(tf("demo.early_return.too_big", &[("value", &n)])));
+    }
+    if n == 13 {
+        return Err(DemoError::Message(t("demo.early_return.unlucky").to_string()));
+    }
+    Ok(n)
+}
+
+// The same checks with `context!`, `ensure!` and `bail!`
+fn checked_after(fs: &dyn FileSystem, path: &Path) -> Result<i32, DemoError> {
+    let text = context!(fs.read_to_string(path), tf("demo.early_return.reading", &[("path", &path.display())]))?;
+    let n: i32 = text.trim().parse()?;
+    ensure!(n % 2 != 0, DemoError::BusinessRule(odd_violation(n)));
+    ensure!(n <= 100, "{}", tf("demo.early_return.too_big", &[("value", &n)]));
+    if n == 13 {
+        bail!("{}", t("demo.early_return.unlucky"));
+    }
+    Ok(n)
+}
+
+// Unchanged for a boxed error: the macros convert into whatever the function returns
+fn checked_boxed(fs: &dyn FileSystem, path: &Path) -> Result<i32, Box<dyn Error>> {
+    let text = context!(fs.read_to_string(path), tf("demo.early_return.reading", &[("path", &path.display())]))?;
+    let n: i32 = text.trim().parse()?;
+    ensure!(n % 2 != 0, DemoError::BusinessRule(odd_violation(n)));
+    ensure!(n <= 100, "{}", tf("demo.early_return.too_big", &[("value", &n)]));
+    if n == 13 {
+        bail!("{}", t("demo.early_return.unlucky"));
+    }
+    Ok(n)
+}
+
+struct Combinators;
+
+impl Demo for Combinators {
+    fn name(&self) -> &'static str { "combinators" }
+    fn title(&self) -> &'static str { t("demo.combinators.title") }
+    fn description(&self) -> &'static str { t("demo.combinators.description") }
+    fn tags(&self) -> &'static [&'static str] { &["result"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        Ok(demonstrate_combinators(&mut ctx.out)?)
+    }
+}
+
+struct CustomError;
+
+impl Demo for CustomError {
+    fn name(&self) -> &'static str { "custom-error" }
+    fn title(&self) -> &'static str { t("demo.custom_error.title") }
+    fn description(&self) -> &'static str { t("demo.custom_error.description") }
+    fn tags(&self) -> &'static [&'static str] { &["custom-error", "io"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        match read_number_from_file(ctx.fs.as_ref(), &ctx.numbers_path) {
+            Ok(n) => writeln!(ctx.out, "{}", tf("demo.custom_error.ok", &[("value", &n)]))?,
+            Err(DemoError::Diagnostic(d)) => writeln!(ctx.err, "{}", d.render())?,
+            Err(e) => writeln!(ctx.err, "{}", tf("demo.custom_error.failed", &[("error", &chain::plain(&e))]))?,
+        }
+        Ok(())
+    }
+}
+
+struct BoxedError;
+
+impl Demo for BoxedError {
+    fn name(&self) -> &'static str { "boxed-error" }
+    fn title(&self) -> &'static str { t("demo.boxed_error.title") }
+    fn description(&self) -> &'static str { t("demo.boxed_error.description") }
+    fn tags(&self) -> &'static [&'static str] { &["boxed", "io"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        let boxed: Result<u32, Box<dyn Error>> = read_value_generic(ctx.fs.as_ref(), &ctx.numbers_path);
+        match boxed {
+            Ok(n) => writeln!(ctx.out, "{}", tf("demo.boxed_error.ok", &[("value", &n)]))?,
+            Err(e) => writeln!(ctx.err, "{}", tf("demo.boxed_error.failed", &[("error", &e)]))?,
+        }
+        Ok(())
+    }
+}
+
+struct Reports;
+
+impl Demo for Reports {
+    fn name(&self) -> &'static str { "report" }
+    fn title(&self) -> &'static str { t("demo.report.title") }
+    fn description(&self) -> &'static str { t("demo.report.description") }
+    fn tags(&self) -> &'static [&'static str] { &["boxed", "report", "downcast"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        let fs = MemoryFs::new().file("typo.txt", "4x2\n");
+        for name in ["typo.txt", "missing.txt"] {
+            let path = Path::new(name);
+            let boxed = read_value_generic::<u32>(&fs, path).err().ok_or(t("demo.report.unexpected_ok"))?;
+            let report = read_value_report::<u32>(&fs, path).err().ok_or(t("demo.report.unexpected_ok"))?;
+            writeln!(ctx.out, "{}:", name)?;
+            writeln!(ctx.out, "  Box<dyn Error> => {}", boxed)?;
+            writeln!(ctx.out, "  Report         => {:#}", report)?;
+        }
+
+        // Both keep a `DemoError` intact, but only the report looks past it for the `io::Error`
+        let missing = || read_number_from_file(&fs, Path::new("missing.txt")).unwrap_err();
+        let boxed: Box<dyn Error> = Box::new(missing());
+        let report = Report::from(missing()).context(t("demo.report.loading"));
+        writeln!(ctx.out, "\n{}", t("demo.report.downcast"))?;
+        let found = |demo: bool, io: bool| format!("DemoError {}, io::Error {}", demo, io);
+        writeln!(ctx.out, "  Box<dyn Error> => {}", found(boxed.is::<DemoError>(), boxed.is::<io::Error>()))?;
+        let (demo, io) = (report.downcast_ref::<DemoError>(), report.downcast_ref::<io::Error>());
+        writeln!(ctx.out, "  Report         => {}", found(demo.is_some(), io.is_some()))?;
+        writeln!(ctx.out, "  code           => {:?}", demo.map(DemoError::code))?;
+
+        let (report, boxed) = (std::mem::size_of::<Report>(), std::mem::size_of::<Box<dyn Error>>());
+        writeln!(ctx.out, "\n{}", tf("demo.report.size", &[("report", &report), ("boxed", &boxed)]))?;
+        Ok(())
+    }
+}
+
+struct Recover;
+
+impl Demo for Recover {
+    fn name(&self) -> &'static str { "recovery" }
+    fn title(&self) -> &'static str { t("demo.recovery.title") }
+    fn description(&self) -> &'static str { t("demo.recovery.description") }
+    fn tags(&self) -> &'static [&'static str] { &["boxed", "downcast", "io"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        let fs = MemoryFs::new().file("typo.txt", "4x2\n");
+        // What the user types when asked; scripted so the demo runs unattended
+        let mut answers = io::Cursor::new("seven\n7\n");
+        for (i, name) in ["missing.txt", "typo.txt"].into_iter().enumerate() {
+            writeln!(ctx.out, "{}{}:", if i > 0 { "\n" } else { "" }, name)?;
+            let n = read_recovering(&mut ctx.out, &fs, Path::new(name), &mut answers)?;
+            writeln!(ctx.out, "  => Ok({})", n)?;
+        }
+        Ok(())
+    }
+}
+
+// Read a number, letting the type of whatever went wrong decide how to recover
+fn read_recovering(
+    out: &mut dyn Write,
+    fs: &dyn FileSystem,
+    path: &Path,
+    input: &mut dyn BufRead,
+) -> Result<u32, Box<dyn Error>> {
+    for _ in 0..5 {
+        // Erased on purpose: all we get to work with is a `Box<dyn Error>`
+        let err: Box<dyn Error> = match read_number_from_file(fs, path) {
+            Ok(n) => return Ok(n),
+            Err(e) => Box::new(e),
+        };
+        let types: Vec<&str> = recovery::known(err.as_ref()).map(Known::type_name).collect();
+        let recovery = Recovery::for_error(err.as_ref());
+        writeln!(out, "  {}", tf("demo.recovery.failed", &[("error", &err)]))?;
+        writeln!(out, "  {} {} -> {:?}", t("demo.recovery.found"), types.join(" > "), recovery)?;
+        match recovery {
+            Recovery::CreateDefault => {
+                fs.write(path, b"0\n")?;
+                writeln!(out, "  {}", tf("demo.recovery.created", &[("path", &path.display())]))?;
+            }
+            Recovery::AskAgain => {
+                writeln!(out, "  {}", tf("demo.recovery.prompt", &[("path", &path.display())]))?;
+                let mut line = String::new();
+                if input.read_line(&mut line)? == 0 {
+                    return Err(err);
+                }
+                writeln!(out, "  > {}", line.trim_end())?;
+                fs.write(path, line.as_bytes())?;
+            }
+            Recovery::Retry => {}
+            Recovery::GiveUp => return Err(err),
+        }
+    }
+    Err(t("demo.recovery.too_many").into())
+}
+
+struct ErrorChain;
+
+impl Demo for ErrorChain {
+    fn name(&self) -> &'static str { "error-chain" }
+    fn title(&self) -> &'static str { t("demo.error_chain.title") }
+    fn description(&self) -> &'static str { t("demo.error_chain.description") }
+    fn tags(&self) -> &'static [&'static str] { &["custom-error", "context"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        let port = "80a";
+        let err = match load_port(port) {
+            Ok(_) => return Ok(()),
+            Err(e) => e,
+        };
+        writeln!(ctx.out, "Display  => {}", err)?;
+        writeln!(ctx.out, "plain    =>\n{}", chain::plain(&err))?;
+        writeln!(ctx.out, "json     => {}", chain::json(&err))?;
+        Ok(())
+    }
+}
+
+// Two layers of context over a ParseIntError: what was parsed, and why
+fn load_port(text: &str) -> Result<u16, DemoError> {
+    let parse = || text.parse::<u16>().with_context(|| tf("context.parse_number", &[("text", &text)]));
+    parse().context(t("demo.error_chain.loading_config"))
+}
+
+struct Diagnostics;
+
+impl Demo for Diagnostics {
+    fn name(&self) -> &'static str { "diagnostics" }
+    fn title(&self) -> &'static str { t("demo.diagnostics.title") }
+    fn description(&self) -> &'static str { t("demo.diagnostics.description") }
+    fn tags(&self) -> &'static [&'static str] { &["parse", "diagnostics"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        // Each sample is what a broken numbers.txt might contain
+        let samples = ["", "  -42\n", "4294967296\n", "\n12x4\n", "  12\n3x\n"];
+        for contents in samples {
+            let trimmed = contents.trim();
+            if let Err(e) = trimmed.parse::<u32>() {
+                let diag = Diagnostic::for_token::<u32>(Path::new("numbers.txt"), contents, trimmed, e);
+                writeln!(ctx.out, "{}\n", diag.render())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct GenericValues;
+
+impl Demo for GenericValues {
+    fn name(&self) -> &'static str { "generic-values" }
+    fn title(&self) -> &'static str { t("demo.generic_values.title") }
+    fn description(&self) -> &'static str { t("demo.generic_values.description") }
+    fn tags(&self) -> &'static [&'static str] { &["parse", "generic"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        let path = Path::new("config.txt");
+        show(&mut ctx.out, "i64", parse_value::<i64>(path, "-17\n"))?;
+        show(&mut ctx.out, "f64", parse_value::<f64>(path, "3.5\n"))?;
+        show(&mut ctx.out, "bool", parse_value::<bool>(path, "yes\n"))?;
+        show(&mut ctx.out, "Ipv4Addr", parse_value::<Ipv4Addr>(path, "10.0.0.300\n"))?;
+        show(&mut ctx.out, "Celsius", parse_value::<Celsius>(path, "21.5C\n"))?;
+        show(&mut ctx.out, "Celsius", parse_value::<Celsius>(path, "-300C\n"))?;
+        Ok(())
+    }
+}
+
+struct VirtualFs;
+
+impl Demo for VirtualFs {
+    fn name(&self) -> &'static str { "virtual-fs" }
+    fn title(&self) -> &'static str { t("demo.virtual_fs.title") }
+    fn description(&self) -> &'static str { t("demo.virtual_fs.description") }
+    fn tags(&self) -> &'static [&'static str] { &["io", "fs"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        // Every way reading a file can go wrong, without touching the disk
+        let fs = MemoryFs::new()
+            .file("good.txt", "42\n")
+            .failing("locked.txt", io::ErrorKind::PermissionDenied)
+            .partial("cut.txt", "4", io::ErrorKind::UnexpectedEof)
+            .file("corrupt.txt", b"4\xff2\n".to_vec())
+            .file("typo.txt", "4x2\n");
+        for name in ["good.txt", "missing.txt", "locked.txt", "cut.txt", "corrupt.txt", "typo.txt"] {
+            show(&mut ctx.out, name, read_number_from_file(&fs, Path::new(name)))?;
+        }
+        Ok(())
+    }
+}
+
+struct Retry;
+
+impl Demo for Retry {
+    fn name(&self) -> &'static str { "retry" }
+    fn title(&self) -> &'static str { t("demo.retry.title") }
+    fn description(&self) -> &'static str { t("demo.retry.description") }
+    fn tags(&self) -> &'static [&'static str] { &["io", "retry"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        let path = Path::new("numbers.txt");
+        // Fixed seed so the jittered delays are the same on every run
+        let policy = RetryPolicy::new(3)
+            .backoff(Backoff::Exponential { initial: Duration::from_millis(2), max: Duration::from_millis(8) })
+            .jitter(0.5)
+            .seed(42);
+        let scenarios = [
+            ("demo.retry.recovers", &policy, flaky(io::ErrorKind::TimedOut, Site::Open, Some(2))),
+            ("demo.retry.gives_up", &policy, flaky(io::ErrorKind::Interrupted, Site::Read, None)),
+            ("demo.retry.permanent", &policy, flaky(io::ErrorKind::NotFound, Site::Open, None)),
+        ];
+        // Waits on a manual clock, so a stalled machine cannot move the deadline
+        let slow = RetryPolicy::new(10)
+            .backoff(Backoff::Fixed(Duration::from_millis(40)))
+            .deadline(Duration::from_millis(50))
+            .clock(ManualClock::default());
+        let deadline = ("demo.retry.deadline", &slow, flaky(io::ErrorKind::WouldBlock, Site::Open, None));
+
+        for (i, (label, policy, fs)) in scenarios.into_iter().chain([deadline]).enumerate() {
+            writeln!(ctx.out, "{}{}", if i > 0 { "\n" } else { "" }, t(label))?;
+            let out = &mut ctx.out;
+            let result = policy.run_notify(
+                |_| read_number_from_file(&fs, path),
+                |attempt, e, delay| {
+                    let delay = format!("{:.2}ms", delay.as_secs_f64() * 1000.0);
+                    // The innermost cause says what actually went wrong
+                    let cause = chain::causes(e).last().map_or(e.to_string(), |c| c.to_string());
+                    let args: [(&str, &dyn Display); 3] = [("attempt", &attempt), ("error", &cause), ("delay", &delay)];
+                    let _ = writeln!(out, "  {}", tf("demo.retry.attempt_failed", &args));
+                },
+            );
+            match result {
+                Ok(n) => writeln!(ctx.out, "  => Ok({})", n)?,
+                Err(e) => writeln!(ctx.out, "{}", chain::plain(&e))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// `numbers.txt` holds 7, but opening or reading it fails `times` times (`None`: always)
+fn flaky(kind: io::ErrorKind, site: Site, times: Option<usize>) -> FaultyFs {
+    let fs = MemoryFs::new().file("numbers.txt", "7\n");
+    let injection = Injection { fault: Fault::Io { kind, site }, times };
+    FaultyFs::new(Box::new(fs), Plan { injections: vec![injection], chaos: None })
+}
+
+fn show<T: Display>(out: &mut dyn Write, label: &str, result: Result<T, DemoError>) -> io::Result<()> {
+    match result {
+        Ok(v) => writeln!(out, "{:<9} => Ok({})", label, v)?,
+        Err(e) => writeln!(out, "{:<9} => {}", label, chain::plain(&e))?,
+    }
+    Ok(())
+}
+
+// A custom type with its own parse error, to show the reader is not tied to std types
+#[derive(Debug)]
+struct Celsius(f64);
+
+impl Display for Celsius {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}°C", self.0)
+    }
+}
 
-// A small custom error to show how to define and use your own error types
 #[derive(Debug)]
-enum DemoError {
-    Io(io::Error),
-    Parse(ParseIntError),
-    BusinessRule(&'static str),
+enum CelsiusError {
+    MissingUnit,
+    BelowAbsoluteZero(f64),
+    Number(ParseFloatError),
 }
 
-impl Display for DemoError {
+impl Display for CelsiusError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            DemoError::Io(e) => write!(f, "IO error: {}", e),
-            DemoError::Parse(e) => write!(f, "Parse error: {}", e),
-            DemoError::BusinessRule(msg) => write!(f, "Business error: {}", msg),
+            CelsiusError::MissingUnit => write!(f, "{}", t("demo.generic_values.missing_unit")),
+            CelsiusError::BelowAbsoluteZero(v) => {
+                write!(f, "{}", tf("demo.generic_values.below_zero", &[("value", v)]))
+            }
+            CelsiusError::Number(e) => write!(f, "{}", e),
         }
     }
 }
 
-impl Error for DemoError {
+impl Error for CelsiusError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            DemoError::Io(e) => Some(e),
-            DemoError::Parse(e) => Some(e),
-            DemoError::BusinessRule(_) => None,
+            CelsiusError::Number(e) => Some(e),
+            _ => None,
         }
     }
 }
 
-impl From<io::Error> for DemoError {
-    fn from(err: io::Error) -> Self { DemoError::Io(err) }
+impl FromStr for Celsius {
+    type Err = CelsiusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = s.strip_suffix('C').ok_or(CelsiusError::MissingUnit)?;
+        let value: f64 = number.parse().map_err(CelsiusError::Number)?;
+        if value < -273.15 {
+            return Err(CelsiusError::BelowAbsoluteZero(value));
+        }
+        Ok(Celsius(value))
+    }
 }
 
-impl From<ParseIntError> for DemoError {
-    fn from(err: ParseIntError) -> Self { DemoError::Parse(err) }
+struct BusinessRules;
+
+impl Demo for BusinessRules {
+    fn name(&self) -> &'static str { "business-rule" }
+    fn title(&self) -> &'static str { t("demo.business_rule.title") }
+    fn description(&self) -> &'static str { t("demo.business_rule.description") }
+    fn tags(&self) -> &'static [&'static str] { &["custom-error", "rules"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        let err = DemoError::BusinessRule(odd_violation(42));
+        writeln!(ctx.out, "Display => {}", err)?;
+        if let DemoError::BusinessRule(v) = &err {
+            writeln!(ctx.out, "code    => {}", v.code())?;
+            let expected = v.expected.as_ref().map_or("-".to_string(), |c| c.to_string());
+            writeln!(ctx.out, "rule    => {}, value => {}, expected => {}", v.rule, v.value, expected)?;
+        }
+        Ok(())
+    }
 }
 
-// Public entry to run all demos
-pub fn run() -> Result<(), Box<dyn Error>> {
-    println!("=== Result 基本用法 ===");
-    basics()?;
+fn odd_violation(n: i32) -> RuleViolation {
+    RuleViolation::new("odd", "rule.odd", i64::from(n)).expected(Constraint::Odd)
+}
 
-    println!("\n=== ? 运算符传播错误 ===");
-    demonstrate_question_mark_operator()?;
+struct RulesEngine;
 
-    println!("\n=== map / map_err / and_then 组合器 ===");
-    demonstrate_combinators();
+impl Demo for RulesEngine {
+    fn name(&self) -> &'static str { "rules-engine" }
+    fn title(&self) -> &'static str { t("demo.rules_engine.title") }
+    fn description(&self) -> &'static str { t("demo.rules_engine.description") }
+    fn tags(&self) -> &'static [&'static str] { &["rules", "diagnostics"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        let path = Path::new("rules.txt");
+        let rules = RuleSet::parse(path, "odd\nbetween 1 and 1000\ndivisible by 7 # lucky\nnot in 13, 42, 777\n")?;
+        for n in [49, 42, 1001, 777] {
+            let violations = rules.check(n).into_result().err().unwrap_or_default();
+            writeln!(ctx.out, "{} => {}", n, tf("demo.rules_engine.violations", &[("count", &violations.len())]))?;
+            for v in violations {
+                writeln!(ctx.out, "  {}", v)?;
+            }
+        }
 
-    println!("\n=== 自定义错误类型与 From 转换 ===");
-    match read_number_from_file("numbers.txt") {
-        Ok(n) => println!("读取成功: {}", n),
-        Err(e) => eprintln!("读取失败: {}", e),
+        // A typo in the rules file is reported like any other parse failure
+        if let Err(DemoError::Diagnostic(d)) = RuleSet::parse(path, "odd\ndivisible bye 7\n") {
+            writeln!(ctx.out, "\n{}", d.render())?;
+        }
+        Ok(())
     }
+}
+
+struct Validation;
 
-    println!("\n=== 将具体错误抹平为 Box<dyn Error> ===");
-    let boxed: Result<u32, Box<dyn Error>> = read_number_generic("numbers.txt");
-    match boxed {
-        Ok(n) => println!("boxed 读取成功: {}", n),
-        Err(e) => eprintln!("boxed 读取失败: {}", e),
+impl Demo for Validation {
+    fn name(&self) -> &'static str { "validation" }
+    fn title(&self) -> &'static str { t("demo.validation.title") }
+    fn description(&self) -> &'static str { t("demo.validation.description") }
+    fn tags(&self) -> &'static [&'static str] { &["result", "validation", "rules"] }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        let form = SignupForm { age: "17", port: "80a", lucky: "42" };
+
+        writeln!(ctx.out, "{}", t("demo.validation.fail_fast"))?;
+        if let Err(e) = signup_fail_fast(&form) {
+            writeln!(ctx.out, "{}", chain::plain(&e))?;
+        }
+
+        writeln!(ctx.out, "\n{}", t("demo.validation.accumulate"))?;
+        if let Err(e) = signup_validated(&form).into_demo_result() {
+            writeln!(ctx.out, "{}", chain::plain(&e))?;
+        }
+
+        // Once everything is valid, both styles produce the same value
+        let fixed = SignupForm { age: "30", port: "8080", lucky: "7" };
+        let Signup { age, port, lucky } = signup_validated(&fixed).into_demo_result()?;
+        writeln!(ctx.out, "\nage = {}, port = {}, lucky = {}", age, port, lucky)?;
+        Ok(())
     }
+}
 
-    Ok(())
+// Raw text as typed into a form
+struct SignupForm<'a> {
+    age: &'a str,
+    port: &'a str,
+    lucky: &'a str,
+}
+
+struct Signup {
+    age: u8,
+    port: u16,
+    lucky: i64,
 }
 
-fn basics() -> Result<(), Box<dyn Error>> {
+fn check_age(text: &str) -> Result<u8, DemoError> {
+    let age: u8 = text.parse().map_err(DemoError::parse::<u8>)?;
+    match (Constraint::Range { min: 18, max: 130 }).check(i64::from(age)) {
+        Some(v) => Err(DemoError::BusinessRule(v)),
+        None => Ok(age),
+    }
+}
+
+fn check_lucky(text: &str) -> Validated<i64, DemoError> {
+    let rules = RuleSet::parse(Path::new("lucky.rules"), "odd\nnot in 13, 42\n");
+    Validated::from(rules.and_then(|rules| Ok((rules, text.parse::<i64>()?))))
+        .and_then(|(rules, n)| rules.check(n))
+}
+
+fn field<T>(name: &str, result: Result<T, DemoError>) -> Result<T, DemoError> {
+    result.with_context(|| tf("demo.validation.field", &[("name", &name)]))
+}
+
+// `?` stops at the first bad field, so the user fixes one problem per round trip
+fn signup_fail_fast(form: &SignupForm) -> Result<Signup, DemoError> {
+    let age = field("age", check_age(form.age))?;
+    let port = field("port", form.port.parse().map_err(DemoError::parse::<u16>))?;
+    let lucky = field("lucky", check_lucky(form.lucky).into_demo_result())?;
+    Ok(Signup { age, port, lucky })
+}
+
+// The same checks, but every field is checked and every problem reported
+fn signup_validated(form: &SignupForm) -> Validated<Signup, DemoError> {
+    let age = Validated::from(field("age", check_age(form.age)));
+    let port = Validated::from(field("port", form.port.parse().map_err(DemoError::parse::<u16>)));
+    let lucky = Validated::from(field("lucky", check_lucky(form.lucky).into_demo_result()));
+    age.zip(port).zip(lucky).map(|((age, port), lucky)| Signup { age, port, lucky })
+}
+
+// The literal Err below is the point of the lesson
+#[allow(clippy::unnecessary_literal_unwrap)]
+fn basics(out: &mut dyn Write) -> Result<(), Box<dyn Error>> {
     let ok_value: Result<i32, &str> = Ok(42);
     let err_value: Result<i32, &str> = Err("boom");
 
     // Inspect results
-    println!("ok_value.is_ok() = {}", ok_value.is_ok());
-    println!("err_value.is_err() = {}", err_value.is_err());
+    writeln!(out, "ok_value.is_ok() = {}", ok_value.is_ok())?;
+    writeln!(out, "err_value.is_err() = {}", err_value.is_err())?;
 
     // Unwrap with default
-    println!("unwrap_or: {}", err_value.unwrap_or(-1));
+    writeln!(out, "unwrap_or: {}", err_value.unwrap_or(-1))?;
 
     // Match
     match ok_value {
-        Ok(v) => println!("match Ok: {}", v),
-        Err(e) => println!("match Err: {}", e),
+        Ok(v) => writeln!(out, "match Ok: {}", v)?,
+        Err(e) => writeln!(out, "match Err: {}", e)?,
     }
 
     Ok(())
 }
 
 // Use ? to propagate errors upward as DemoError
-fn demonstrate_question_mark_operator() -> Result<(), DemoError> {
+fn demonstrate_question_mark_operator(out: &mut dyn Write, fs: &dyn FileSystem, source_path: &Path) -> Result<(), DemoError> {
     // Simulate parsing from string
     let s = "123";
     let n: i32 = s.parse()?; // ParseIntError -> DemoError via From
-    println!("parsed = {}", n);
+    writeln!(out, "parsed = {}", n)?;
 
     // Simulate IO: read current source file just to demo
-    let mut file = File::open("src/main.rs")?;
+    let mut file = fs.open(source_path)?;
     let mut content = String::new();
     file.read_to_string(&mut content)?;
-    println!("main.rs length = {}", content.len());
+    writeln!(out, "main.rs length = {}", content.len())?;
 
     // Trigger a business rule error path
-    if n % 2 == 0 {
-        return Err(DemoError::BusinessRule("数字不能是偶数"));
-    }
+    ensure!(n % 2 != 0, DemoError::BusinessRule(odd_violation(n)));
 
     Ok(())
 }
 
 // Combinators showcase
-fn demonstrate_combinators() {
+fn demonstrate_combinators(out: &mut dyn Write) -> io::Result<()> {
     let input = "10";
 
     // map: transform Ok value
     let doubled = input.parse::<i32>().map(|v| v * 2);
-    println!("map doubled = {:?}", doubled);
+    writeln!(out, "map doubled = {:?}", doubled)?;
 
     // map_err: transform Err value
     let bad_input = "abc";
-    let mapped_err = bad_input.parse::<i32>().map_err(|e| format!("解析失败: {}", e));
-    println!("map_err => {:?}", mapped_err);
+    let mapped_err = bad_input.parse::<i32>().map_err(|e| tf("demo.combinators.parse_failed", &[("error", &e)]));
+    writeln!(out, "map_err => {:?}", mapped_err)?;
 
     // and_then: chain computations that also return Result
     fn reciprocal(x: f64) -> Result<f64, &'static str> {
-        if x == 0.0 { Err("除数不能为 0") } else { Ok(1.0 / x) }
+        if x == 0.0 { Err(t("demo.combinators.divide_by_zero")) } else { Ok(1.0 / x) }
     }
-    let chained = "5".parse::<f64>().map_err(|_| "不是数字").and_then(reciprocal);
-    println!("and_then chained = {:?}", chained);
+    let chained = "5".parse::<f64>().map_err(|_| t("demo.combinators.not_a_number")).and_then(reciprocal);
+    writeln!(out, "and_then chained = {:?}", chained)?;
+    Ok(())
 }
 
 // Read a number from a file, demonstrating custom error usage
-fn read_number_from_file(path: &str) -> Result<u32, DemoError> {
+pub fn read_number_from_file(fs: &dyn FileSystem, path: &Path) -> Result<u32, DemoError> {
+    read_value_from_file(fs, path)
+}
+
+// Read any `FromStr` value from a file; each step says what it was doing
+// so the cause chain reads like a story
+pub fn read_value_from_file<T>(fs: &dyn FileSystem, path: &Path) -> Result<T, DemoError>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
     let mut buf = String::new();
-    File::open(path)?.read_to_string(&mut buf)?;
-    let trimmed = buf.trim();
-    let n: u32 = trimmed.parse()?;
-    Ok(n)
+    fs.open(path)
+        .with_context(|| tf("context.open_numbers", &[("path", &path.display())]))?
+        .read_to_string(&mut buf)
+        .with_context(|| tf("context.read_numbers", &[("path", &path.display())]))?;
+    parse_value(path, &buf)
+}
+
+// Parse the trimmed `contents` of `path` as a `T`, pointing at the problem on failure
+pub fn parse_value<T>(path: &Path, contents: &str) -> Result<T, DemoError>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    let trimmed = contents.trim();
+    trimmed
+        .parse()
+        .map_err(|e| DemoError::Diagnostic(Box::new(Diagnostic::for_token::<T>(path, contents, trimmed, e))))
+}
+
+// The same read returning a `Report`: each step adds context, and the
+// original `io::Error` or parse error can still be downcast afterwards
+pub fn read_value_report<T>(fs: &dyn FileSystem, path: &Path) -> Result<T, Report>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    let mut buf = String::new();
+    fs.open(path)
+        .wrap_err_with(|| tf("context.open_numbers", &[("path", &path.display())]))?
+        .read_to_string(&mut buf)
+        .wrap_err_with(|| tf("context.read_numbers", &[("path", &path.display())]))?;
+    let text = buf.trim();
+    text.parse().wrap_err_with(|| tf("context.parse_number", &[("text", &text)]))
 }
 
 // Erase specific errors into Box<dyn Error>
-fn read_number_generic(path: &str) -> Result<u32, Box<dyn Error>> {
+fn read_value_generic<T>(fs: &dyn FileSystem, path: &Path) -> Result<T, Box<dyn Error>>
+where
+    T: FromStr,
+    T::Err: Error + 'static,
+{
     let mut buf = String::new();
-    File::open(path)?.read_to_string(&mut buf)?;
+    fs.open(path)?.read_to_string(&mut buf)?;
     Ok(buf.trim().parse()?)
 }
-
-
@@ -0,0 +1,210 @@
+use std::cell::Cell;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::fault::Rng;
+use crate::result_demo::DemoError;
+
+// How long to wait before the next attempt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backoff {
+    Fixed(Duration),
+    // `initial`, then doubled after every failure, never more than `max`
+    Exponential { initial: Duration, max: Duration },
+}
+
+impl Backoff {
+    // Delay after failed attempt number `attempt` (1-based), before jitter
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match *self {
+            Backoff::Fixed(delay) => delay,
+            Backoff::Exponential { initial, max } => {
+                let factor = 2u32.checked_pow(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+                initial.checked_mul(factor).map_or(max, |d| d.min(max))
+            }
+        }
+    }
+}
+
+// Where a `RetryPolicy` reads the time and waits between attempts
+pub trait Clock {
+    // Time since some fixed point; only differences between readings matter
+    fn now(&self) -> Duration;
+    fn sleep(&self, duration: Duration);
+}
+
+// The real time, counted from when the clock was made
+pub struct SystemClock(Instant);
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        SystemClock(Instant::now())
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.0.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+// Time that only moves when something sleeps, so deadlines are hit the same
+// way on every run however busy the machine is
+#[derive(Default)]
+pub struct ManualClock(Cell<Duration>);
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.0.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.0.set(self.0.get() + duration);
+    }
+}
+
+// Why a retried operation gave up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GiveUp {
+    MaxAttempts,
+    // Waiting for another attempt would have run past the deadline
+    Deadline,
+    // The last error is not worth retrying
+    Permanent,
+}
+
+impl GiveUp {
+    // Catalog key for the message of a `DemoError::RetriesExhausted` that gave up this way
+    pub fn message_key(self) -> &'static str {
+        match self {
+            GiveUp::MaxAttempts => "error.retry.max_attempts",
+            GiveUp::Deadline => "error.retry.deadline",
+            GiveUp::Permanent => "error.retry.permanent",
+        }
+    }
+}
+
+// When and how often to try an operation again:
+//
+//   let policy = RetryPolicy::new(5)
+//       .backoff(Backoff::Exponential { initial: ms(10), max: ms(200) })
+//       .jitter(0.5)
+//       .deadline(Duration::from_secs(2));
+//   let n = policy.run(|_| read_number_from_file(fs, path))?;
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff: Backoff,
+    // Up to this fraction of each delay is randomly taken off, so clients
+    // that failed together do not all come back at the same moment
+    jitter: f64,
+    seed: Option<u64>,
+    deadline: Option<Duration>,
+    // Whether an error is worth another attempt; `DemoError::is_transient` by default
+    classifier: fn(&DemoError) -> bool,
+    clock: Box<dyn Clock>,
+}
+
+impl RetryPolicy {
+    // `max_attempts` counts the first try; no delay, jitter or deadline by default,
+    // and waits on the `SystemClock`
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts: max_attempts.max(1),
+            backoff: Backoff::Fixed(Duration::ZERO),
+            jitter: 0.0,
+            seed: None,
+            deadline: None,
+            classifier: DemoError::is_transient,
+            clock: Box::new(SystemClock::default()),
+        }
+    }
+
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    // `fraction` is clamped to 0..=1
+    pub fn jitter(mut self, fraction: f64) -> Self {
+        self.jitter = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    // Fixes the jitter sequence, for reproducible runs; otherwise it is seeded from the clock
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn classifier(mut self, classifier: fn(&DemoError) -> bool) -> Self {
+        self.classifier = classifier;
+        self
+    }
+
+    pub fn clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    pub fn run<T>(&self, op: impl FnMut(u32) -> Result<T, DemoError>) -> Result<T, DemoError> {
+        self.run_notify(op, |_, _, _| {})
+    }
+
+    // Like `run`, calling `on_retry(attempt, error, delay)` before each wait.
+    // A failure after the first attempt is `RetriesExhausted` holding every
+    // attempt's error; a permanent error on the first attempt is returned as is
+    pub fn run_notify<T>(
+        &self,
+        mut op: impl FnMut(u32) -> Result<T, DemoError>,
+        mut on_retry: impl FnMut(u32, &DemoError, Duration),
+    ) -> Result<T, DemoError> {
+        let started = self.clock.now();
+        let rng = Rng::new(self.seed.unwrap_or_else(clock_seed));
+        let mut attempts = Vec::new();
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let err = match op(attempt) {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+            let delay = self.jittered(self.backoff.delay(attempt), &rng);
+            let reason = if !(self.classifier)(&err) {
+                Some(GiveUp::Permanent)
+            } else if attempt >= self.max_attempts {
+                Some(GiveUp::MaxAttempts)
+            } else if self.deadline.is_some_and(|d| self.clock.now() - started + delay > d) {
+                Some(GiveUp::Deadline)
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                if attempts.is_empty() {
+                    return Err(err);
+                }
+                attempts.push(err);
+                return Err(DemoError::RetriesExhausted { attempts, reason });
+            }
+            on_retry(attempt, &err, delay);
+            attempts.push(err);
+            self.clock.sleep(delay);
+        }
+    }
+
+    fn jittered(&self, delay: Duration, rng: &Rng) -> Duration {
+        delay.mul_f64(1.0 - self.jitter * rng.unit())
+    }
+}
+
+fn clock_seed() -> u64 {
+    SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64)
+}
@@ -0,0 +1,238 @@
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+use crate::codes;
+use crate::context::ResultExt;
+use crate::diagnostics::Diagnostic;
+use crate::fs::FileSystem;
+use crate::i18n::{t, tf};
+use crate::result_demo::DemoError;
+use crate::validation::Validated;
+
+// What a value was expected to satisfy, shown as `{expected}` in rule messages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Constraint {
+    Odd,
+    Even,
+    // Inclusive on both ends
+    Range { min: i64, max: i64 },
+    DivisibleBy(i64),
+    NotIn(Vec<i64>),
+}
+
+impl Display for Constraint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Constraint::Odd => write!(f, "{}", t("constraint.odd")),
+            Constraint::Even => write!(f, "{}", t("constraint.even")),
+            Constraint::Range { min, max } => {
+                write!(f, "{}", tf("constraint.range", &[("min", min), ("max", max)]))
+            }
+            Constraint::DivisibleBy(d) => write!(f, "{}", tf("constraint.divisible_by", &[("divisor", d)])),
+            Constraint::NotIn(values) => {
+                let list = values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ");
+                write!(f, "{}", tf("constraint.not_in", &[("values", &list)]))
+            }
+        }
+    }
+}
+
+impl Constraint {
+    // Stable identifier used in violation codes
+    pub fn id(&self) -> &'static str {
+        match self {
+            Constraint::Odd => "odd",
+            Constraint::Even => "even",
+            Constraint::Range { .. } => "range",
+            Constraint::DivisibleBy(_) => "divisible_by",
+            Constraint::NotIn(_) => "not_in",
+        }
+    }
+
+    fn template(&self) -> &'static str {
+        match self {
+            Constraint::Odd => "rule.odd",
+            Constraint::Even => "rule.even",
+            Constraint::Range { .. } => "rule.range",
+            Constraint::DivisibleBy(_) => "rule.divisible_by",
+            Constraint::NotIn(_) => "rule.not_in",
+        }
+    }
+
+    pub fn is_satisfied_by(&self, n: i64) -> bool {
+        match self {
+            Constraint::Odd => n % 2 != 0,
+            Constraint::Even => n % 2 == 0,
+            Constraint::Range { min, max } => (*min..=*max).contains(&n),
+            Constraint::DivisibleBy(d) => n.wrapping_rem(*d) == 0,
+            Constraint::NotIn(values) => !values.contains(&n),
+        }
+    }
+
+    // The violation `n` causes, if any
+    pub fn check(&self, n: i64) -> Option<RuleViolation> {
+        if self.is_satisfied_by(n) {
+            return None;
+        }
+        Some(RuleViolation::new(self.id(), self.template(), n).expected(self.clone()))
+    }
+}
+
+// A broken business rule: which rule, which value, and what was expected instead
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleViolation {
+    // Stable identifier of the rule, e.g. `odd` or `range`
+    pub rule: &'static str,
+    pub value: i64,
+    pub expected: Option<Constraint>,
+    // Catalog key of the message; may use `{rule}`, `{value}` and `{expected}`
+    pub template: &'static str,
+}
+
+impl RuleViolation {
+    pub fn new(rule: &'static str, template: &'static str, value: i64) -> Self {
+        RuleViolation { rule, value, expected: None, template }
+    }
+
+    pub fn expected(mut self, constraint: Constraint) -> Self {
+        self.expected = Some(constraint);
+        self
+    }
+
+    // Code from `codes::REGISTRY`; stays the same across languages and message edits
+    pub fn code(&self) -> &'static str {
+        codes::for_rule(self.rule)
+    }
+}
+
+impl Display for RuleViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let expected = self.expected.as_ref().map(|c| c.to_string()).unwrap_or_default();
+        let args: [(&str, &dyn Display); 3] = [("rule", &self.rule), ("value", &self.value), ("expected", &expected)];
+        write!(f, "{}", tf(self.template, &args))
+    }
+}
+
+// Rules loaded from a text file, one per line, `#` starts a comment:
+//
+//   odd
+//   between 1 and 1000
+//   divisible by 7
+//   not in 13, 42, 666
+#[derive(Debug, Default)]
+pub struct RuleSet {
+    // Each rule with the line it was defined on
+    rules: Vec<(usize, Constraint)>,
+}
+
+impl RuleSet {
+    pub fn load(fs: &dyn FileSystem, path: &Path) -> Result<RuleSet, DemoError> {
+        let text = fs.read_to_string(path).with_context(|| tf("context.open_rules", &[("path", &path.display())]))?;
+        RuleSet::parse(path, &text)
+    }
+
+    // `path` only labels diagnostics; the first malformed line is reported
+    pub fn parse(path: &Path, text: &str) -> Result<RuleSet, DemoError> {
+        let mut rules = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            let parser = LineParser { path, line_no: i + 1, line, code: line.split('#').next().unwrap_or("") };
+            if let Some(rule) = parser.rule().map_err(DemoError::Diagnostic)? {
+                rules.push((i + 1, rule));
+            }
+        }
+        Ok(RuleSet { rules })
+    }
+
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Constraint> {
+        self.rules.iter().map(|(_, rule)| rule)
+    }
+
+    // `n` if it passes every rule, otherwise every rule it breaks in file order
+    pub fn check(&self, n: i64) -> Validated<i64, DemoError> {
+        let violations: Vec<DemoError> = self.iter().filter_map(|rule| rule.check(n)).map(DemoError::BusinessRule).collect();
+        Validated::check(n, violations)
+    }
+}
+
+struct LineParser<'a> {
+    path: &'a Path,
+    line_no: usize,
+    line: &'a str,
+    // `line` without its comment
+    code: &'a str,
+}
+
+impl<'a> LineParser<'a> {
+    fn rule(&self) -> Result<Option<Constraint>, Box<Diagnostic>> {
+        let mut words = self.code.split(|c: char| c == ',' || c.is_whitespace()).filter(|w| !w.is_empty());
+        let Some(keyword) = words.next() else {
+            return Ok(None);
+        };
+        let rule = match keyword {
+            "odd" => Constraint::Odd,
+            "even" => Constraint::Even,
+            "between" => {
+                let min = self.number(words.next())?;
+                self.keyword(words.next(), "and", "rules.expected_and")?;
+                let max_word = words.next();
+                let max = self.number(max_word)?;
+                if min > max {
+                    return Err(self.error(max_word.unwrap_or(self.end()), "rules.empty_range"));
+                }
+                Constraint::Range { min, max }
+            }
+            "divisible" => {
+                self.keyword(words.next(), "by", "rules.expected_by")?;
+                let word = words.next();
+                match self.number(word)? {
+                    0 => return Err(self.error(word.unwrap_or(self.end()), "rules.zero_divisor")),
+                    d => Constraint::DivisibleBy(d),
+                }
+            }
+            "not" => {
+                self.keyword(words.next(), "in", "rules.expected_in")?;
+                let values = words.by_ref().map(|w| self.number(Some(w))).collect::<Result<Vec<_>, _>>()?;
+                if values.is_empty() {
+                    return Err(self.error(self.end(), "rules.empty_list"));
+                }
+                Constraint::NotIn(values)
+            }
+            _ => return Err(self.error(keyword, "rules.unknown_rule")),
+        };
+        match words.next() {
+            Some(extra) => Err(self.error(extra, "rules.trailing")),
+            None => Ok(Some(rule)),
+        }
+    }
+
+    fn number(&self, word: Option<&'a str>) -> Result<i64, Box<Diagnostic>> {
+        let word = word.ok_or_else(|| self.error(self.end(), "rules.expected_number"))?;
+        word.parse().map_err(|e| Box::new(Diagnostic::for_line::<i64>(self.path, self.line_no, self.line, word, e)))
+    }
+
+    fn keyword(&self, word: Option<&'a str>, expected: &str, key: &'static str) -> Result<(), Box<Diagnostic>> {
+        match word {
+            Some(w) if w == expected => Ok(()),
+            Some(w) => Err(self.error(w, key)),
+            None => Err(self.error(self.end(), key)),
+        }
+    }
+
+    // Empty slice just past the last non-blank character, for "missing" errors
+    fn end(&self) -> &'a str {
+        let end = self.code.trim_end().len();
+        &self.line[end..end]
+    }
+
+    fn error(&self, token: &str, key: &'static str) -> Box<Diagnostic> {
+        Box::new(Diagnostic::syntax(self.path, self.line_no, self.line, token, key))
+    }
+}
@@ -0,0 +1,140 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::chain;
+use crate::demo::{self, Context, Demo, Registry};
+use crate::fs::MemoryFs;
+use crate::i18n::{self, Locale};
+
+// Where a demo's golden output lives, one file per locale: `basics.en.txt`
+pub fn path(dir: &Path, demo: &dyn Demo, locale: Locale) -> PathBuf {
+    dir.join(format!("{}.{}.txt", demo.name(), locale.tag()))
+}
+
+// Run `demo` with its output captured and lay out everything it printed:
+//
+//   --- stdout ---
+//   ...
+//   --- stderr ---
+//   ...
+//   --- result ---
+//   ok
+pub fn render(demo: &dyn Demo, ctx: &mut Context) -> String {
+    let (out, err) = ctx.capture();
+    let result = match demo::run_guarded(demo, ctx) {
+        Ok(()) => "ok".to_string(),
+        Err(e) => format!("failed: {}", chain::plain(e.as_ref())),
+    };
+    let text = format!("--- stdout ---\n{}--- stderr ---\n{}--- result ---\n{}\n", out.text(), err.text(), result);
+    without_line_numbers(&text)
+}
+
+// Source locations such as panic sites move whenever code above them is
+// edited, so `src/panic_demo.rs:107:65` is kept as `src/panic_demo.rs:LINE:COL`
+pub fn without_line_numbers(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find(".rs:") {
+        let (before, after) = rest.split_at(at + ".rs:".len());
+        normalized.push_str(before);
+        rest = after;
+        if let Some(tail) = line_and_column(rest) {
+            normalized.push_str("LINE:COL");
+            rest = tail;
+        }
+    }
+    normalized.push_str(rest);
+    normalized
+}
+
+// What follows `<line>:<column>` at the start of `text`, if it starts that way
+fn line_and_column(text: &str) -> Option<&str> {
+    let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let line = digits(text);
+    let rest = text[line..].strip_prefix(':').filter(|_| line > 0)?;
+    let column = digits(rest);
+    (column > 0).then(|| &rest[column..])
+}
+
+// Snapshots must not depend on the directory they run in, so demos read
+// from memory: `numbers.txt` is missing and `src/main.rs` is a stub
+pub fn fixture() -> Context {
+    let fs = MemoryFs::new().file("src/main.rs", "fn main() {}\n");
+    Context { fs: Box::new(fs), ..Context::default() }
+}
+
+// How a rendered demo compared with its snapshot file
+#[derive(Debug, PartialEq, Eq)]
+pub enum Status {
+    Matched,
+    // The snapshot was (re)written from the current output
+    Updated,
+    Missing,
+    // Holds a `-expected` / `+actual` listing of the lines that differ
+    Differs(String),
+}
+
+#[derive(Debug)]
+pub struct Checked {
+    pub demo: &'static str,
+    pub locale: Locale,
+    pub path: PathBuf,
+    pub status: Status,
+}
+
+impl Checked {
+    pub fn passed(&self) -> bool {
+        matches!(self.status, Status::Matched | Status::Updated)
+    }
+}
+
+// Compare every demo in every locale against the snapshots in `dir`, or
+// rewrite them when `update` is set. The current locale is restored afterwards
+pub fn check_all(registry: &Registry, dir: &Path, update: bool) -> io::Result<Vec<Checked>> {
+    let previous = i18n::locale();
+    let result = check_locales(registry, dir, update);
+    i18n::set_locale(previous);
+    result
+}
+
+fn check_locales(registry: &Registry, dir: &Path, update: bool) -> io::Result<Vec<Checked>> {
+    let mut checked = Vec::new();
+    for locale in Locale::ALL {
+        i18n::set_locale(locale);
+        for demo in registry.iter() {
+            let path = path(dir, demo, locale);
+            let actual = render(demo, &mut fixture());
+            let status = if update {
+                fs::create_dir_all(dir)?;
+                fs::write(&path, &actual)?;
+                Status::Updated
+            } else {
+                match fs::read_to_string(&path) {
+                    Ok(expected) if expected == actual => Status::Matched,
+                    Ok(expected) => Status::Differs(diff(&expected, &actual)),
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => Status::Missing,
+                    Err(e) => return Err(e),
+                }
+            };
+            checked.push(Checked { demo: demo.name(), locale, path, status });
+        }
+    }
+    Ok(checked)
+}
+
+// Line-by-line comparison: every position where the two differ, as `-` and `+` lines
+fn diff(expected: &str, actual: &str) -> String {
+    let (expected, actual): (Vec<&str>, Vec<&str>) = (expected.lines().collect(), actual.lines().collect());
+    let mut out = Vec::new();
+    for i in 0..expected.len().max(actual.len()) {
+        let (e, a) = (expected.get(i), actual.get(i));
+        if e == a {
+            continue;
+        }
+        out.push(format!("@@ {} @@", i + 1));
+        out.extend(e.map(|l| format!("-{}", l)));
+        out.extend(a.map(|l| format!("+{}", l)));
+    }
+    out.join("\n")
+}
@@ -0,0 +1,92 @@
+use crate::result_demo::DemoError;
+
+// Like `Result`, but combining two failures keeps the errors of both instead
+// of stopping at the first, so a form can report every problem at once
+#[derive(Debug)]
+pub enum Validated<T, E> {
+    Valid(T),
+    // Never empty
+    Invalid(Vec<E>),
+}
+
+impl<T, E> Validated<T, E> {
+    pub fn invalid(err: E) -> Self {
+        Validated::Invalid(vec![err])
+    }
+
+    // `value` unless there are `errors`; keeps `Invalid` from ever being empty
+    pub fn check(value: T, errors: Vec<E>) -> Self {
+        if errors.is_empty() { Validated::Valid(value) } else { Validated::Invalid(errors) }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, Validated::Valid(_))
+    }
+
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Validated<U, E> {
+        match self {
+            Validated::Valid(v) => Validated::Valid(f(v)),
+            Validated::Invalid(errors) => Validated::Invalid(errors),
+        }
+    }
+
+    // Combine two independent checks; errors from both sides are kept, in order
+    pub fn zip<U>(self, other: Validated<U, E>) -> Validated<(T, U), E> {
+        match (self, other) {
+            (Validated::Valid(a), Validated::Valid(b)) => Validated::Valid((a, b)),
+            (Validated::Invalid(errors), Validated::Valid(_)) | (Validated::Valid(_), Validated::Invalid(errors)) => {
+                Validated::Invalid(errors)
+            }
+            (Validated::Invalid(mut a), Validated::Invalid(b)) => {
+                a.extend(b);
+                Validated::Invalid(a)
+            }
+        }
+    }
+
+    // A check that needs the value of this one, so it cannot run if this one failed
+    pub fn and_then<U, F: FnOnce(T) -> Validated<U, E>>(self, f: F) -> Validated<U, E> {
+        match self {
+            Validated::Valid(v) => f(v),
+            Validated::Invalid(errors) => Validated::Invalid(errors),
+        }
+    }
+
+    pub fn into_result(self) -> Result<T, Vec<E>> {
+        match self {
+            Validated::Valid(v) => Ok(v),
+            Validated::Invalid(errors) => {
+                debug_assert!(!errors.is_empty(), "`Validated::Invalid` with no errors");
+                Err(errors)
+            }
+        }
+    }
+}
+
+impl<T> Validated<T, DemoError> {
+    // Back to a plain `Result`, so `?` works again; several errors become `DemoError::Multiple`
+    pub fn into_demo_result(self) -> Result<T, DemoError> {
+        self.into_result().map_err(|errors| DemoError::from_errors(errors).expect("`Validated::Invalid` is never empty"))
+    }
+}
+
+impl<T, E> From<Result<T, E>> for Validated<T, E> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(v) => Validated::Valid(v),
+            Err(e) => Validated::invalid(e),
+        }
+    }
+}
+
+// Validate every item, keeping all errors: `Valid` only if every item was
+impl<T, E> FromIterator<Validated<T, E>> for Validated<Vec<T>, E> {
+    fn from_iter<I: IntoIterator<Item = Validated<T, E>>>(iter: I) -> Self {
+        iter.into_iter().fold(Validated::Valid(Vec::new()), |acc, item| {
+            acc.zip(item).map(|(mut values, v)| {
+                values.push(v);
+                values
+            })
+        })
+    }
+}
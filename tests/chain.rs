@@ -0,0 +1,51 @@
+// Which causes get a line of their own when a chain is shown to people
+use std::io;
+
+use learn::chain;
+use learn::i18n::{self, Locale};
+use learn::json::Value;
+use learn::result_demo::DemoError;
+
+fn context(context: &str, source: DemoError) -> DemoError {
+    DemoError::Context { context: context.to_string(), source: Box::new(source) }
+}
+
+fn not_found() -> DemoError {
+    DemoError::Io(io::Error::new(io::ErrorKind::NotFound, "no such file"))
+}
+
+#[test]
+fn the_source_of_io_and_parse_errors_is_not_repeated() {
+    i18n::set_locale(Locale::En);
+    let err = context("opening `n.txt`", not_found());
+    assert_eq!(chain::plain(&err), "opening `n.txt`\n  caused by: IO error: no such file");
+    assert_eq!(chain::causes(&err).count(), 3);
+
+    let err = context("reading the port", DemoError::from("x".parse::<u16>().unwrap_err()));
+    assert_eq!(chain::shown(&err).count(), 2);
+}
+
+#[test]
+fn causes_with_empty_or_repeated_messages_are_kept() {
+    let err = context("checking", DemoError::Message(String::new()));
+    assert_eq!(chain::shown(&err).count(), 2);
+
+    let err = context("same text", DemoError::Message("same text".to_string()));
+    assert_eq!(chain::shown(&err).count(), 2);
+}
+
+#[test]
+fn plain_and_json_list_the_same_grouped_errors() {
+    i18n::set_locale(Locale::En);
+    let err = context("loading", DemoError::Multiple(vec![not_found(), context("inner", not_found())]));
+    assert_eq!(
+        chain::plain(&err),
+        "loading\n  caused by: 2 errors\n    - IO error: no such file\n    - inner\n      caused by: IO error: no such file"
+    );
+    let Value::Object(fields) = chain::json_value(&err) else { panic!("not an object") };
+    let grouped = fields.iter().find(|(k, _)| *k == "errors").map(|(_, v)| v.to_string());
+    assert_eq!(
+        grouped.as_deref(),
+        Some(r#"[{"error":"IO error: no such file","causes":[]},{"error":"inner","causes":["IO error: no such file"]}]"#)
+    );
+}
@@ -0,0 +1,54 @@
+// Exit code, category and code of a type-erased error all come from the same
+// error in its chain, so they cannot disagree
+use std::error::Error;
+use std::io;
+use std::net::Ipv4Addr;
+
+use learn::category::Category;
+use learn::chain;
+use learn::codes;
+use learn::exit;
+use learn::result_demo::DemoError;
+
+fn boxed<E: Error + 'static>(err: E) -> Box<dyn Error> {
+    Box::new(err)
+}
+
+#[test]
+fn std_errors_are_classified_like_the_demo_error_they_convert_into() {
+    let cases: Vec<(Box<dyn Error>, DemoError)> = vec![
+        (boxed("x".parse::<i32>().unwrap_err()), DemoError::from("x".parse::<i32>().unwrap_err())),
+        (boxed("".parse::<u8>().unwrap_err()), DemoError::from("".parse::<u8>().unwrap_err())),
+        (boxed("x".parse::<f64>().unwrap_err()), DemoError::from("x".parse::<f64>().unwrap_err())),
+        (boxed("yes".parse::<bool>().unwrap_err()), DemoError::from("yes".parse::<bool>().unwrap_err())),
+        (boxed("10.0.0.300".parse::<Ipv4Addr>().unwrap_err()), DemoError::parse::<Ipv4Addr>("10.0.0.300".parse::<Ipv4Addr>().unwrap_err())),
+        (boxed(io::Error::from(io::ErrorKind::NotFound)), DemoError::from(io::Error::from(io::ErrorKind::NotFound))),
+        (boxed(io::Error::from(io::ErrorKind::TimedOut)), DemoError::from(io::Error::from(io::ErrorKind::TimedOut))),
+    ];
+    for (err, demo) in cases {
+        let class = chain::classify(err.as_ref()).unwrap_or_else(|| panic!("{:?} is not classified", err));
+        assert_eq!(class.code, demo.code(), "{:?}", err);
+        assert_eq!(class.category, demo.category(), "{:?}", err);
+        assert_eq!(class.exit_code, exit::for_demo_error(&demo), "{:?}", err);
+        assert_eq!(codes::of(err.as_ref()), Some(class.code));
+        assert_eq!(Category::of(err.as_ref()), class.category);
+        assert_eq!(exit::for_error(err.as_ref()), class.exit_code);
+    }
+}
+
+#[test]
+fn a_boxed_parse_int_error_is_bad_input() {
+    let err = boxed("x".parse::<i32>().unwrap_err());
+    assert_eq!(exit::for_error(err.as_ref()), exit::DATA_ERR);
+    assert_eq!(Category::of(err.as_ref()), Category::User);
+    assert_eq!(codes::of(err.as_ref()), Some("E0104"));
+}
+
+#[test]
+fn unknown_errors_are_bugs() {
+    let err: Box<dyn Error> = "something else".into();
+    assert_eq!(chain::classify(err.as_ref()), None);
+    assert_eq!(exit::for_error(err.as_ref()), exit::SOFTWARE);
+    assert_eq!(Category::of(err.as_ref()), Category::Bug);
+    assert_eq!(codes::of(err.as_ref()), None);
+}
@@ -0,0 +1,122 @@
+// Command-line parsing and demo selection
+use learn::cli::{self, Cli, Command, Output, UsageError};
+use learn::demo::Registry;
+use learn::fault::Plan;
+use learn::i18n::Locale;
+
+fn parse(args: &[&str]) -> Result<Cli, UsageError> {
+    cli::parse(args.iter().map(|a| a.to_string()))
+}
+
+fn run(names: &[&str], all: bool, filter: Option<&str>) -> Command {
+    let names = names.iter().map(|n| n.to_string()).collect();
+    Command::Run { names, all, filter: filter.map(str::to_string), output: Output::Text, faults: Plan::default(), isolate: false }
+}
+
+#[test]
+fn no_arguments_run_everything() {
+    assert_eq!(parse(&[]).unwrap(), Cli { command: run(&[], true, None), lang: None });
+}
+
+#[test]
+fn flags_can_come_anywhere() {
+    let cli = parse(&["--lang", "en", "run", "basics", "--output", "json", "question-mark"]).unwrap();
+    assert_eq!(cli.lang, Some(Locale::En));
+    match cli.command {
+        Command::Run { names, output, .. } => {
+            assert_eq!(names, ["basics", "question-mark"]);
+            assert_eq!(output, Output::Json);
+        }
+        other => panic!("expected run, got {:?}", other),
+    }
+    assert_eq!(parse(&["run", "--filter", "option"]).unwrap().command, run(&[], false, Some("option")));
+    assert_eq!(parse(&["numbers", "n.txt", "--keep-going"]).unwrap().command, Command::Numbers { path: "n.txt".into(), keep_going: true });
+}
+
+#[test]
+fn help_wins_over_everything_else() {
+    assert_eq!(parse(&["run", "basics", "--help"]).unwrap().command, Command::Help);
+    assert_eq!(parse(&["help"]).unwrap().command, Command::Help);
+}
+
+fn usage_error(args: &[&str]) -> UsageError {
+    match parse(args) {
+        Err(e) => e,
+        Ok(cli) => panic!("{:?} parsed as {:?}", args, cli),
+    }
+}
+
+#[test]
+fn usage_errors() {
+    assert!(matches!(usage_error(&["frobnicate"]), UsageError::UnknownCommand(c) if c == "frobnicate"));
+    assert!(matches!(usage_error(&["run", "--verbose"]), UsageError::UnknownFlag(f) if f == "--verbose"));
+    assert!(matches!(usage_error(&["run", "--filter"]), UsageError::MissingValue("--filter")));
+    assert!(matches!(usage_error(&["--lang", "fr"]), UsageError::UnknownLocale(l) if l == "fr"));
+    assert!(matches!(usage_error(&["run", "--all", "--output", "xml"]), UsageError::UnknownOutput(o) if o == "xml"));
+    assert!(matches!(usage_error(&["run", "--all", "--inject", "io:bogus@open"]), UsageError::UnknownFault(_)));
+    assert!(matches!(usage_error(&["run", "--all", "--chaos", "-1"]), UsageError::InvalidSeed(_)));
+    assert!(matches!(usage_error(&["run"]), UsageError::NothingToRun));
+    assert!(matches!(usage_error(&["run", "basics", "--all"]), UsageError::NamesWithAll));
+    assert!(matches!(usage_error(&["rules", "r.txt"]), UsageError::MissingArgument("<numbers-file>")));
+    assert!(matches!(usage_error(&["list", "extra"]), UsageError::UnexpectedArgument(a) if a == "extra"));
+    assert!(matches!(usage_error(&["numbers", "f", "--inject", "parse"]), UsageError::RunOnly("--inject")));
+    assert!(matches!(usage_error(&["list", "--chaos", "7"]), UsageError::RunOnly("--chaos")));
+    assert!(matches!(usage_error(&["snapshot", "--isolate"]), UsageError::RunOnly("--isolate")));
+}
+
+#[test]
+fn names_select_demos_in_the_given_order() {
+    let registry = Registry::builtin();
+    let names = ["question-mark".to_string(), "basics".to_string()];
+    let selected: Vec<&str> = cli::select(&registry, &names, false, None).unwrap().iter().map(|d| d.name()).collect();
+    assert_eq!(selected, ["question-mark", "basics"]);
+}
+
+#[test]
+fn a_filter_that_matches_nothing_is_an_error() {
+    let registry = Registry::builtin();
+    assert!(!cli::select(&registry, &[], true, Some("option")).unwrap().is_empty());
+    assert!(matches!(cli::select(&registry, &[], true, Some("zzz")), Err(UsageError::NoMatch(f)) if f == "zzz"));
+}
+
+fn suggestions(name: &str) -> Vec<&'static str> {
+    match cli::select(&Registry::builtin(), &[name.to_string()], false, None) {
+        Err(UsageError::UnknownDemo { suggestions, .. }) => suggestions,
+        other => panic!("expected an unknown demo, got {:?}", other.map(|d| d.len())),
+    }
+}
+
+#[test]
+fn unknown_demos_get_close_names_suggested() {
+    // A typo is a small edit away
+    assert!(suggestions("basic").contains(&"basics"));
+    assert!(suggestions("qestion-mark").contains(&"question-mark"));
+    // Part of a longer name
+    assert!(suggestions("lookup").contains(&"number-lookup"));
+    // Nothing is close to this one
+    assert_eq!(suggestions("xyzzy-plugh-quux"), Vec::<&str>::new());
+}
+
+#[test]
+fn usage_errors_come_out_in_the_requested_language() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_learn"))
+        .args(["--lang", "en", "run", "--inject", "io:bogus@open"])
+        .env("LANG", "zh_CN.UTF-8")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(64));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with("error: unknown fault `io:bogus@open`"), "{}", stderr);
+    assert!(stderr.contains("Usage:"), "{}", stderr);
+}
+
+#[test]
+fn demos_read_their_files_wherever_learn_runs() {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_learn"))
+        .args(["--lang", "en", "run", "question-mark"])
+        .current_dir(std::env::temp_dir())
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("main.rs length = "));
+}
@@ -0,0 +1,97 @@
+// The error-code registry is append-only: codes must stay unique and well formed,
+// and every code the crate can produce must be explainable
+use std::collections::HashSet;
+use std::io;
+use std::path::Path;
+
+use learn::codes::{self, REGISTRY};
+use learn::diagnostics::{Diagnostic, ParseIssue};
+use learn::i18n::{self, Locale};
+use learn::isolate::Termination;
+use learn::result_demo::DemoError;
+use learn::retry::GiveUp;
+use learn::rules::RuleViolation;
+
+#[test]
+fn codes_are_unique_and_well_formed() {
+    let mut seen = HashSet::new();
+    for entry in REGISTRY {
+        assert!(seen.insert(entry.code), "{} is registered twice", entry.code);
+        let digits = entry.code.strip_prefix('E').unwrap_or("");
+        assert!(digits.len() == 4 && digits.bytes().all(|b| b.is_ascii_digit()), "{} is not E + 4 digits", entry.code);
+    }
+}
+
+#[test]
+fn every_entry_is_translated() {
+    for entry in REGISTRY {
+        for key in [entry.title, entry.explanation, entry.fix] {
+            for locale in Locale::ALL {
+                assert_ne!(i18n::lookup(locale, key), key, "{} has no `{}` message", entry.code, key);
+            }
+        }
+        assert!(!entry.reproducer.is_empty(), "{} has no reproducer", entry.code);
+    }
+}
+
+// One error of every kind the crate can produce. The match in
+// `every_produced_code_is_registered` fails to compile when a variant is added,
+// so a new one cannot be forgotten here
+fn every_error() -> Vec<DemoError> {
+    let kinds = [
+        io::ErrorKind::NotFound,
+        io::ErrorKind::PermissionDenied,
+        io::ErrorKind::TimedOut,
+        io::ErrorKind::Interrupted,
+        io::ErrorKind::Other,
+    ];
+    let issues = [
+        ParseIssue::Empty,
+        ParseIssue::Negative,
+        ParseIssue::Overflow,
+        ParseIssue::InvalidDigit('x'),
+        ParseIssue::Other,
+        ParseIssue::MultipleLines,
+        ParseIssue::Syntax("rules.trailing"),
+    ];
+    let rules = ["odd", "even", "range", "divisible_by", "not_in", "custom"];
+    let terminations = [Termination::Exit(66), Termination::Signal(11), Termination::Aborted];
+
+    let mut errors: Vec<DemoError> = kinds.into_iter().map(|k| DemoError::Io(k.into())).collect();
+    errors.extend(["", "x", "99999999999999999999"].map(|s| DemoError::from(s.parse::<i64>().unwrap_err())));
+    errors.push(DemoError::from("x".parse::<f64>().unwrap_err()));
+    errors.push(DemoError::from("x".parse::<bool>().unwrap_err()));
+    errors.push(DemoError::MissingKey("key".to_string()));
+    errors.extend(rules.map(|r| DemoError::BusinessRule(RuleViolation::new(r, "rule.odd", 1))));
+    errors.push(DemoError::Message("message".to_string()));
+    errors.extend(issues.map(|issue| DemoError::Diagnostic(Box::new(Diagnostic { issue, ..Diagnostic::syntax(Path::new("f"), 1, "x", "x", "") }))));
+    errors.push(DemoError::Context { context: "context".to_string(), source: Box::new(DemoError::Message(String::new())) });
+    errors.push(DemoError::Multiple(vec![DemoError::Message(String::new())]));
+    errors.push(DemoError::Panicked { message: String::new(), location: String::new() });
+    errors.extend(terminations.map(|termination| DemoError::Isolated { demo: "demo".to_string(), termination, reported: None }));
+    errors.push(DemoError::RetriesExhausted { attempts: vec![], reason: GiveUp::MaxAttempts });
+    errors
+}
+
+#[test]
+fn every_produced_code_is_registered() {
+    let mut variants = HashSet::new();
+    for err in every_error() {
+        // Add a sample to `every_error` for any variant this match gains
+        match &err {
+            DemoError::Io(_)
+            | DemoError::Parse { .. }
+            | DemoError::MissingKey(_)
+            | DemoError::BusinessRule(_)
+            | DemoError::Message(_)
+            | DemoError::Diagnostic(_)
+            | DemoError::Context { .. }
+            | DemoError::Multiple(_)
+            | DemoError::Panicked { .. }
+            | DemoError::Isolated { .. }
+            | DemoError::RetriesExhausted { .. } => variants.insert(err.variant_name()),
+        };
+        assert!(codes::lookup(err.code()).is_some(), "{} ({:?}) is produced but not registered", err.code(), err);
+    }
+    assert_eq!(variants.len(), 11, "every variant needs a sample");
+}
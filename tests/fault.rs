@@ -0,0 +1,38 @@
+// `--isolate` hands faults to each child as text, so writing one out must read back the same
+use learn::cli::{self, Command};
+use learn::fault::{Fault, Injection, Plan, Site, KINDS};
+
+fn every_injection() -> Vec<Injection> {
+    let mut faults = vec![Fault::Parse];
+    for &(_, kind) in KINDS {
+        faults.push(Fault::Io { kind, site: Site::Open });
+        faults.push(Fault::Io { kind, site: Site::Read });
+    }
+    faults.into_iter().flat_map(|fault| [None, Some(0), Some(3)].map(|times| Injection { fault, times })).collect()
+}
+
+#[test]
+fn injections_round_trip_through_their_text() {
+    for injection in every_injection() {
+        assert_eq!(Injection::parse(&injection.to_string()), Some(injection.clone()), "{}", injection);
+    }
+    assert_eq!(Injection::parse("io:timed_out@read*2").unwrap().to_string(), "io:timed_out@read*2");
+}
+
+#[test]
+fn malformed_specs_are_rejected() {
+    for spec in ["", "io:", "io:not_found", "io:not_found@write", "io:nope@open", "parse*", "parse*x", "parse*-1", "garble"] {
+        assert_eq!(Injection::parse(spec), None, "{}", spec);
+    }
+}
+
+#[test]
+fn plans_round_trip_through_their_args() {
+    let faults = Plan { injections: every_injection(), chaos: Some(42) };
+    let mut args = vec!["run".to_string(), "basics".to_string()];
+    args.extend(faults.args());
+    match cli::parse(args).unwrap().command {
+        Command::Run { faults: parsed, .. } => assert_eq!(parsed, faults),
+        other => panic!("expected run, got {:?}", other),
+    }
+}
@@ -0,0 +1,19 @@
+// Placeholders are filled in once, left to right
+use learn::i18n::interpolate;
+
+#[test]
+fn every_placeholder_is_replaced() {
+    let out = interpolate("{a} and {b}, {a} again", &[("a", &1), ("b", &"two")]);
+    assert_eq!(out, "1 and two, 1 again");
+}
+
+#[test]
+fn unknown_placeholders_and_stray_braces_are_kept() {
+    assert_eq!(interpolate("{missing} {x} { {", &[("x", &7)]), "{missing} 7 { {");
+}
+
+#[test]
+fn values_are_not_interpolated_again() {
+    let out = interpolate("panicked at {location}: {message}", &[("message", &"see {location}"), ("location", &"src/main.rs:4:21")]);
+    assert_eq!(out, "panicked at src/main.rs:4:21: see {location}");
+}
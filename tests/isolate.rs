@@ -0,0 +1,72 @@
+// `learn run --isolate` turns however a child process ended into a DemoError
+use std::process::Command;
+
+use learn::isolate::Termination;
+
+#[cfg(unix)]
+#[test]
+fn wait_statuses_map_to_terminations() {
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    // Raw wait statuses: exit codes live in the high byte, signals in the low one
+    assert_eq!(Termination::of(ExitStatus::from_raw(0)), None);
+    assert_eq!(Termination::of(ExitStatus::from_raw(66 << 8)), Some(Termination::Exit(66)));
+    assert_eq!(Termination::of(ExitStatus::from_raw(6)), Some(Termination::Aborted));
+    assert_eq!(Termination::of(ExitStatus::from_raw(9)), Some(Termination::Signal(9)));
+}
+
+#[test]
+fn terminations_have_codes_and_exit_codes() {
+    assert_eq!(Termination::Exit(66).code(), "E0402");
+    assert_eq!(Termination::Signal(11).code(), "E0403");
+    assert_eq!(Termination::Aborted.code(), "E0404");
+
+    // A child's own exit code is passed on, anything else is a bug
+    assert_eq!(Termination::Exit(66).exit_code(), 66);
+    assert_eq!(Termination::Exit(-1).exit_code(), 70);
+    assert_eq!(Termination::Signal(11).exit_code(), 70);
+    assert_eq!(Termination::Aborted.exit_code(), 70);
+}
+
+fn learn(args: &[&str]) -> (Option<i32>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_learn")).args(args).output().unwrap();
+    (output.status.code(), String::from_utf8(output.stdout).unwrap())
+}
+
+#[test]
+fn an_isolated_run_reports_every_demo() {
+    let (code, out) = learn(&["run", "basics", "--isolate", "--lang", "en"]);
+    assert_eq!(code, Some(0), "{}", out);
+    assert!(out.contains("=== Isolated run: 0 of 1 demos failed ==="), "{}", out);
+}
+
+#[test]
+fn a_failing_child_does_not_stop_the_rest() {
+    let (code, out) = learn(&["run", "question-mark", "basics", "--isolate", "--inject", "io:not_found@open", "--lang", "en"]);
+    assert_eq!(code, Some(66), "{}", out);
+    assert!(out.contains("match Ok: 42"), "{}", out);
+    assert!(out.contains("question-mark        [E0402] (environment problem)"), "{}", out);
+    assert!(out.contains("after failing with E0001"), "{}", out);
+    assert!(out.contains("basics               ok"), "{}", out);
+}
+
+#[test]
+fn json_records_say_how_each_child_ended() {
+    let (code, out) = learn(&["run", "question-mark", "basics", "--isolate", "--inject", "io:not_found@open", "--output", "json", "--lang", "en"]);
+    assert_eq!(code, Some(66), "{}", out);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 2, "{}", out);
+    assert!(lines[0].contains(r#""code":"E0001""#), "{}", lines[0]);
+    assert!(lines[0].contains(r#""plain":"IO error: entity not found""#), "{}", lines[0]);
+    assert!(lines[0].ends_with(r#","isolation":{"termination":"exit","exit_status":66,"signal":null,"code":"E0402"}}"#), "{}", lines[0]);
+    assert!(lines[1].ends_with(r#","isolation":{"termination":"ok","exit_status":0,"signal":null,"code":null}}"#), "{}", lines[1]);
+}
+
+#[test]
+fn the_reported_code_does_not_depend_on_the_language() {
+    let (code, out) = learn(&["run", "question-mark", "--isolate", "--inject", "io:not_found@open", "--lang", "zh-CN"]);
+    assert_eq!(code, Some(66), "{}", out);
+    assert!(out.contains("question-mark        [E0402] (运行环境问题)"), "{}", out);
+    assert!(out.contains("此前以 E0001 失败"), "{}", out);
+}
@@ -0,0 +1,80 @@
+// The hand-written serializer behind `--output json`; CI parses what it writes
+use learn::json::{self, Value};
+
+#[test]
+fn strings_escape_quotes_backslashes_and_control_characters() {
+    assert_eq!(json::string(r#"say "hi""#), r#""say \"hi\"""#);
+    assert_eq!(json::string(r"C:\tmp"), r#""C:\\tmp""#);
+    assert_eq!(json::string("a\nb\r\tc"), r#""a\nb\r\tc""#);
+    assert_eq!(json::string("\u{0}\u{1b}\u{1f}"), r#""\u0000\u001b\u001f""#);
+    // Everything from space upwards, including non-ASCII, is written as is
+    assert_eq!(json::string(" ~\u{7f}中文"), "\" ~\u{7f}中文\"");
+}
+
+#[test]
+fn numbers_are_plain_and_non_finite_ones_are_null() {
+    assert_eq!(Value::Number(42.0).to_string(), "42");
+    assert_eq!(Value::Number(-0.5).to_string(), "-0.5");
+    assert_eq!(Value::Number(0.023).to_string(), "0.023");
+    assert_eq!(Value::Number(f64::NAN).to_string(), "null");
+    assert_eq!(Value::Number(f64::INFINITY).to_string(), "null");
+    assert_eq!(Value::Number(f64::NEG_INFINITY).to_string(), "null");
+}
+
+#[test]
+fn scalars_and_options() {
+    assert_eq!(Value::Null.to_string(), "null");
+    assert_eq!(Value::from(true).to_string(), "true");
+    assert_eq!(Value::from(None::<&str>).to_string(), "null");
+    assert_eq!(Value::from(Some(7u8)).to_string(), "7");
+}
+
+#[test]
+fn nested_values_are_compact_and_keep_key_order() {
+    let value = Value::object([
+        ("name", Value::from("a\"b")),
+        ("empty", Value::Array(vec![])),
+        ("lines", Value::from(vec!["x", "y\n"])),
+        ("inner", Value::object([("z", Value::Null), ("a", Value::Array(vec![Value::object([])]))])),
+    ]);
+    assert_eq!(value.to_string(), r#"{"name":"a\"b","empty":[],"lines":["x","y\n"],"inner":{"z":null,"a":[{}]}}"#);
+}
+
+#[test]
+fn what_display_writes_parses_back() {
+    let value = Value::object([
+        ("name", Value::from("tab\t\"quote\" \\ 中文 \u{1}")),
+        ("n", Value::Number(-12.5e3)),
+        ("flags", Value::Array(vec![Value::from(true), Value::from(false), Value::Null])),
+        ("inner", Value::object([("empty", Value::object([])), ("list", Value::Array(vec![]))])),
+    ]);
+    assert_eq!(json::parse(&value.to_string()), Some(value));
+}
+
+#[test]
+fn parsing_allows_whitespace_and_every_escape() {
+    let value = json::parse(" { \"a\" : [ 1 , \"\\/\\b\\f\\u00e9\\ud83d\\ude00\" ] } \n").unwrap();
+    let items = value.get("a").and_then(Value::as_array).unwrap();
+    assert_eq!(items[0], Value::Number(1.0));
+    assert_eq!(items[1].as_str(), Some("/\u{8}\u{c}é😀"));
+    assert_eq!(value.get("b"), None);
+}
+
+#[test]
+fn anything_but_one_json_document_is_rejected() {
+    let cases = [
+        "", "demo failed [E0001]", "{", "{\"a\"}", "{\"a\":1,}", "[1 2]", "\"open", "\"\\x\"", "\"\\u12\"",
+        "\"\\ud83d\"", "\"a\nb\"", "nul", "+1", "inf", "NaN", "{} {}",
+    ];
+    for text in cases {
+        assert_eq!(json::parse(text), None, "{:?}", text);
+    }
+}
+
+#[test]
+fn with_adds_or_replaces_a_field_at_the_end() {
+    let value = Value::object([("a", Value::Null), ("b", Value::Null)]);
+    assert_eq!(value.clone().with("c", Value::from(1u8)).to_string(), r#"{"a":null,"b":null,"c":1}"#);
+    assert_eq!(value.with("a", Value::from(true)).to_string(), r#"{"b":null,"a":true}"#);
+    assert_eq!(Value::Null.with("a", Value::Null), Value::Null);
+}
@@ -0,0 +1,110 @@
+// Reading one number per line and summarizing them
+use std::io::{self, Cursor};
+use std::path::Path;
+use std::process::Command;
+
+use learn::diagnostics::ParseIssue;
+use learn::numbers::{summarize, ErrorMode, NumberReader};
+use learn::result_demo::DemoError;
+
+fn reader(text: &'static [u8]) -> NumberReader<Cursor<&'static [u8]>> {
+    NumberReader::new(Path::new("numbers.txt"), Cursor::new(text))
+}
+
+// The line of a failed item and what was wrong with it
+fn failure(item: &Result<(usize, u32), DemoError>) -> Option<(usize, ParseIssue)> {
+    match item {
+        Err(DemoError::Diagnostic(d)) => Some((d.line, d.issue)),
+        _ => None,
+    }
+}
+
+#[test]
+fn comments_and_blank_lines_are_skipped_but_counted() {
+    let items: Vec<_> = reader(b"# header\n12\n\n  7  # lucky\n   \n30\n").collect();
+    let numbers: Vec<(usize, u32)> = items.into_iter().map(Result::unwrap).collect();
+    assert_eq!(numbers, [(2, 12), (4, 7), (6, 30)]);
+}
+
+#[test]
+fn bad_lines_are_reported_with_their_line_number() {
+    let items: Vec<_> = reader(b"1\n\n2x\n-3\n4\n").collect();
+    assert_eq!(items.len(), 4);
+    assert_eq!(failure(&items[1]), Some((3, ParseIssue::InvalidDigit('x'))));
+    assert_eq!(failure(&items[2]), Some((4, ParseIssue::Negative)));
+    assert_eq!(items[3].as_ref().unwrap(), &(5, 4));
+}
+
+#[test]
+fn an_io_error_ends_the_stream() {
+    // Invalid UTF-8 makes `lines()` fail on line 2
+    let mut items = reader(b"1\n\xff\n3\n");
+    assert_eq!(items.next().unwrap().unwrap(), (1, 1));
+    match items.next() {
+        Some(Err(DemoError::Context { source, .. })) => match *source {
+            DemoError::Io(e) => assert_eq!(e.kind(), io::ErrorKind::InvalidData),
+            other => panic!("expected an IO error, got {:?}", other),
+        },
+        other => panic!("expected a read error, got {:?}", other),
+    }
+    assert!(items.next().is_none());
+}
+
+#[test]
+fn summary_has_count_sum_min_and_max() {
+    let summary = summarize(reader(b"5\n# skip\n2\n9\n"), ErrorMode::FailFast).unwrap();
+    assert_eq!((summary.count, summary.sum, summary.min, summary.max), (3, 16, Some(2), Some(9)));
+    assert!(summary.errors.is_empty());
+
+    let empty = summarize(reader(b"# nothing\n"), ErrorMode::FailFast).unwrap();
+    assert_eq!((empty.count, empty.sum, empty.min, empty.max), (0, 0, None, None));
+
+    // The sum does not overflow where a u32 would
+    let big = summarize(reader(b"4294967295\n4294967295\n"), ErrorMode::FailFast).unwrap();
+    assert_eq!(big.sum, 2 * u64::from(u32::MAX));
+}
+
+#[test]
+fn fail_fast_stops_at_the_first_bad_line() {
+    let err = summarize(reader(b"1\nx\n2\ny\n"), ErrorMode::FailFast).unwrap_err();
+    assert_eq!(failure(&Err(err)), Some((2, ParseIssue::InvalidDigit('x'))));
+}
+
+#[test]
+fn collect_all_keeps_going_past_bad_lines() {
+    let summary = summarize(reader(b"1\nx\n2\ny\n"), ErrorMode::CollectAll).unwrap();
+    assert_eq!((summary.count, summary.sum), (2, 3));
+    let lines: Vec<_> = summary.errors.into_iter().map(|e| failure(&Err(e)).map(|(line, _)| line)).collect();
+    assert_eq!(lines, [Some(2), Some(4)]);
+}
+
+#[test]
+fn collect_all_still_stops_at_an_io_error() {
+    let err = summarize(reader(b"x\n\xff\n3\n"), ErrorMode::CollectAll).unwrap_err();
+    assert!(matches!(err, DemoError::Context { .. }), "{:?}", err);
+}
+
+#[test]
+fn learn_numbers_prints_the_summary() {
+    let path = std::env::temp_dir().join(format!("learn-numbers-{}.txt", std::process::id()));
+    std::fs::write(&path, "# scores\n12\n7x\n30\n").unwrap();
+    let run = |keep_going: bool| {
+        let mut cmd = Command::new(env!("CARGO_BIN_EXE_learn"));
+        cmd.args(["--lang", "en", "numbers"]).arg(&path);
+        if keep_going {
+            cmd.arg("--keep-going");
+        }
+        cmd.output().unwrap()
+    };
+
+    let stopped = run(false);
+    assert_eq!(stopped.status.code(), Some(65));
+    assert!(stopped.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&stopped.stderr).starts_with("error[E0104]: invalid digit 'x'"));
+
+    let kept = run(true);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(kept.status.code(), Some(65));
+    let stdout = String::from_utf8(kept.stdout).unwrap();
+    assert_eq!(stdout, "count   2\nsum     42\nmin     12\nmax     30\nerrors  1\n");
+}
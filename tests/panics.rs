@@ -0,0 +1,57 @@
+// A demo that panics fails like any other instead of taking the whole run down
+use std::error::Error;
+use std::io::Write;
+
+use learn::demo::{self, Context, Demo};
+use learn::result_demo::DemoError;
+use learn::snapshot;
+
+struct Panicky;
+
+impl Demo for Panicky {
+    fn name(&self) -> &'static str { "panicky" }
+    fn title(&self) -> &'static str { "Panicky" }
+    fn description(&self) -> &'static str { "indexes an empty list" }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        writeln!(ctx.out, "before")?;
+        let empty: Vec<i32> = Vec::new();
+        writeln!(ctx.out, "{}", empty[0])?;
+        Ok(())
+    }
+}
+
+struct Steady;
+
+impl Demo for Steady {
+    fn name(&self) -> &'static str { "steady" }
+    fn title(&self) -> &'static str { "Steady" }
+    fn description(&self) -> &'static str { "prints a line" }
+    fn run(&self, ctx: &mut Context) -> Result<(), Box<dyn Error>> {
+        writeln!(ctx.out, "still running")?;
+        Ok(())
+    }
+}
+
+#[test]
+fn a_panic_becomes_a_panicked_error() {
+    let mut ctx = snapshot::fixture();
+    let (out, _) = ctx.capture();
+    let err = demo::run_guarded(&Panicky, &mut ctx).unwrap_err();
+    match err.downcast_ref::<DemoError>() {
+        Some(DemoError::Panicked { message, location }) => {
+            assert!(message.starts_with("index out of bounds"), "{}", message);
+            assert!(location.starts_with("tests/panics.rs:"), "{}", location);
+        }
+        other => panic!("expected DemoError::Panicked, got {:?}", other),
+    }
+    assert_eq!(out.lines(), ["before"]);
+}
+
+#[test]
+fn the_next_demo_still_runs() {
+    let mut ctx = snapshot::fixture();
+    let (out, _) = ctx.capture();
+    assert!(demo::run_guarded(&Panicky, &mut ctx).is_err());
+    assert!(demo::run_guarded(&Steady, &mut ctx).is_ok());
+    assert_eq!(out.lines(), ["before", "still running"]);
+}
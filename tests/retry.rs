@@ -0,0 +1,135 @@
+// When a RetryPolicy tries again, how long it waits, and why it gives up
+use std::cell::Cell;
+use std::io;
+use std::time::Duration;
+
+use learn::result_demo::DemoError;
+use learn::retry::{Backoff, GiveUp, ManualClock, RetryPolicy};
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+fn transient() -> DemoError {
+    io::Error::from(io::ErrorKind::TimedOut).into()
+}
+
+fn permanent() -> DemoError {
+    io::Error::from(io::ErrorKind::NotFound).into()
+}
+
+fn transients(n: usize) -> Vec<fn() -> DemoError> {
+    vec![transient; n]
+}
+
+// Runs `policy` on an operation that fails with `errors[attempt - 1]`, then
+// succeeds with the attempt number; returns the result and every delay waited
+fn run(policy: &RetryPolicy, errors: &[fn() -> DemoError]) -> (Result<u32, DemoError>, Vec<Duration>) {
+    let mut delays = Vec::new();
+    let result = policy.run_notify(
+        |attempt| match errors.get(attempt as usize - 1) {
+            Some(error) => Err(error()),
+            None => Ok(attempt),
+        },
+        |_, _, delay| delays.push(delay),
+    );
+    (result, delays)
+}
+
+fn gave_up(result: Result<u32, DemoError>) -> (GiveUp, usize) {
+    match result {
+        Err(DemoError::RetriesExhausted { attempts, reason }) => (reason, attempts.len()),
+        other => panic!("expected RetriesExhausted, got {:?}", other),
+    }
+}
+
+#[test]
+fn transient_errors_are_retried_until_one_succeeds() {
+    let policy = RetryPolicy::new(3).clock(ManualClock::default());
+    let (result, delays) = run(&policy, &[transient, transient]);
+    assert_eq!(result.unwrap(), 3);
+    assert_eq!(delays, [Duration::ZERO; 2]);
+}
+
+#[test]
+fn a_permanent_first_error_is_returned_as_is() {
+    let calls = Cell::new(0);
+    let policy = RetryPolicy::new(5).clock(ManualClock::default());
+    let result = policy.run(|_| -> Result<(), DemoError> {
+        calls.set(calls.get() + 1);
+        Err(permanent())
+    });
+    assert!(matches!(result, Err(DemoError::Io(e)) if e.kind() == io::ErrorKind::NotFound));
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn a_permanent_error_after_retries_keeps_every_attempt() {
+    let policy = RetryPolicy::new(5).clock(ManualClock::default());
+    let (result, delays) = run(&policy, &[transient, permanent]);
+    assert_eq!(gave_up(result), (GiveUp::Permanent, 2));
+    assert_eq!(delays.len(), 1);
+}
+
+#[test]
+fn the_classifier_decides_what_is_worth_retrying() {
+    let never = RetryPolicy::new(5).classifier(|_| false).clock(ManualClock::default());
+    assert!(matches!(run(&never, &[transient]).0, Err(DemoError::Io(_))));
+
+    let always = RetryPolicy::new(5).classifier(|_| true).clock(ManualClock::default());
+    assert_eq!(run(&always, &[permanent, permanent]).0.unwrap(), 3);
+}
+
+#[test]
+fn max_attempts_counts_the_first_try() {
+    let policy = RetryPolicy::new(3).clock(ManualClock::default());
+    let (result, delays) = run(&policy, &transients(5));
+    assert_eq!(gave_up(result), (GiveUp::MaxAttempts, 3));
+    assert_eq!(delays.len(), 2);
+
+    // Zero attempts still means one try
+    let once = RetryPolicy::new(0).clock(ManualClock::default());
+    assert!(matches!(run(&once, &[transient]).0, Err(DemoError::Io(_))));
+}
+
+#[test]
+fn the_deadline_stops_retries_that_would_wait_past_it() {
+    // 40ms apart: the second wait would end at 80ms, past the 50ms deadline
+    let policy = RetryPolicy::new(10).backoff(Backoff::Fixed(ms(40))).deadline(ms(50)).clock(ManualClock::default());
+    let (result, delays) = run(&policy, &transients(10));
+    assert_eq!(gave_up(result), (GiveUp::Deadline, 2));
+    assert_eq!(delays, [ms(40)]);
+
+    // With room for every wait, attempts run out first
+    let policy = RetryPolicy::new(3).backoff(Backoff::Fixed(ms(40))).deadline(ms(80)).clock(ManualClock::default());
+    assert_eq!(gave_up(run(&policy, &transients(10)).0), (GiveUp::MaxAttempts, 3));
+}
+
+#[test]
+fn exponential_backoff_doubles_up_to_the_cap() {
+    let backoff = Backoff::Exponential { initial: ms(10), max: ms(200) };
+    let delays: Vec<Duration> = (1..=7).map(|attempt| backoff.delay(attempt)).collect();
+    assert_eq!(delays, [ms(10), ms(20), ms(40), ms(80), ms(160), ms(200), ms(200)]);
+    assert_eq!(backoff.delay(0), ms(10));
+    assert_eq!(Backoff::Fixed(ms(7)).delay(100), ms(7));
+}
+
+#[test]
+fn huge_attempt_numbers_and_delays_saturate_at_the_cap() {
+    let backoff = Backoff::Exponential { initial: ms(10), max: ms(200) };
+    // 2^32 and beyond do not fit the multiplier
+    assert_eq!(backoff.delay(33), ms(200));
+    assert_eq!(backoff.delay(u32::MAX), ms(200));
+    // Nor does doubling the largest possible delay
+    let backoff = Backoff::Exponential { initial: Duration::MAX, max: Duration::from_secs(1) };
+    assert_eq!(backoff.delay(2), Duration::from_secs(1));
+}
+
+#[test]
+fn seeded_jitter_is_reproducible_and_only_shortens_delays() {
+    let policy = || RetryPolicy::new(6).backoff(Backoff::Fixed(ms(100))).jitter(0.5).seed(7).clock(ManualClock::default());
+    let (_, first) = run(&policy(), &transients(6));
+    let (_, second) = run(&policy(), &transients(6));
+    assert_eq!(first, second);
+    assert!(first.iter().all(|d| (ms(50)..=ms(100)).contains(d)), "{:?}", first);
+}
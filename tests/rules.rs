@@ -0,0 +1,69 @@
+// A malformed rules file is reported at the line and column of the problem
+use std::path::Path;
+
+use learn::diagnostics::{Diagnostic, ParseIssue};
+use learn::result_demo::DemoError;
+use learn::rules::RuleSet;
+
+// The diagnostic for `line`, placed on line 2 after a valid rule
+fn diagnose(line: &str) -> Box<Diagnostic> {
+    match RuleSet::parse(Path::new("rules.txt"), &format!("odd\n{}\n", line)) {
+        Err(DemoError::Diagnostic(d)) => d,
+        other => panic!("expected a diagnostic for {:?}, got {:?}", line, other),
+    }
+}
+
+#[test]
+fn valid_rules_and_comments_parse() {
+    let rules = RuleSet::parse(Path::new("rules.txt"), "# comment\nodd\n\nbetween 1 and 10 # inclusive\nnot in 3, 5\n").unwrap();
+    assert_eq!(rules.len(), 3);
+}
+
+#[test]
+fn syntax_errors_point_at_the_offending_word() {
+    let cases = [
+        // line, key, column, width
+        ("prime", "rules.unknown_rule", 1, 5),
+        ("between 10 or 20", "rules.expected_and", 12, 2),
+        ("divisible bye 7", "rules.expected_by", 11, 3),
+        ("not within 3", "rules.expected_in", 5, 6),
+        ("between 20 and 10", "rules.empty_range", 16, 2),
+        ("divisible by 0", "rules.zero_divisor", 14, 1),
+        ("odd please", "rules.trailing", 5, 6),
+    ];
+    for (line, key, column, width) in cases {
+        let d = diagnose(line);
+        assert_eq!(d.issue, ParseIssue::Syntax(key), "{}", line);
+        assert_eq!((d.line, d.column, d.width), (2, column, width), "{}", line);
+        assert_eq!(d.code(), "E0106", "{}", line);
+        assert_eq!(d.snippet, line);
+    }
+}
+
+#[test]
+fn missing_words_point_just_past_the_end() {
+    let cases = [
+        ("between 10", "rules.expected_and", 11),
+        ("between 10 and", "rules.expected_number", 15),
+        ("divisible", "rules.expected_by", 10),
+        ("not in  # none yet", "rules.empty_list", 7),
+    ];
+    for (line, key, column) in cases {
+        let d = diagnose(line);
+        assert_eq!(d.issue, ParseIssue::Syntax(key), "{}", line);
+        assert_eq!((d.line, d.column, d.width), (2, column, 1), "{}", line);
+    }
+}
+
+#[test]
+fn a_bad_number_is_a_parse_diagnostic() {
+    let d = diagnose("not in 3, 5x, 7");
+    assert_eq!(d.issue, ParseIssue::InvalidDigit('x'));
+    assert_eq!((d.line, d.column, d.width), (2, 12, 1));
+    assert_eq!(d.code(), "E0104");
+
+    let d = diagnose("divisible by 99999999999999999999");
+    assert_eq!(d.issue, ParseIssue::Overflow);
+    assert_eq!((d.line, d.column, d.width), (2, 14, 20));
+    assert_eq!(d.code(), "E0102");
+}
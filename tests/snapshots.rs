@@ -0,0 +1,28 @@
+// Golden-output check for every demo in every locale.
+// Set UPDATE_SNAPSHOTS=1 to rewrite the files after an intended change
+use std::path::Path;
+
+use learn::demo::Registry;
+use learn::snapshot::{self, Status};
+
+#[test]
+fn demo_output_matches_snapshots() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("snapshots");
+    let update = std::env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let checked = snapshot::check_all(&Registry::builtin(), &dir, update).expect("snapshot directory is readable");
+    let failures: Vec<String> = checked
+        .iter()
+        .filter(|c| !c.passed())
+        .map(|c| match &c.status {
+            Status::Differs(diff) => format!("{} differs:\n{}", c.path.display(), diff),
+            _ => format!("{} is missing", c.path.display()),
+        })
+        .collect();
+    assert!(failures.is_empty(), "{}\n\nrerun with UPDATE_SNAPSHOTS=1 if the change is intended", failures.join("\n\n"));
+}
+
+#[test]
+fn source_locations_lose_their_line_numbers() {
+    let text = "panicked at src/panic_demo.rs:107:65: boom\nsee lib.rs:12 and main.rs:3:x, 4:5";
+    assert_eq!(snapshot::without_line_numbers(text), "panicked at src/panic_demo.rs:LINE:COL: boom\nsee lib.rs:12 and main.rs:3:x, 4:5");
+}
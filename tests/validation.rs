@@ -0,0 +1,68 @@
+// `Validated` keeps every error where `Result` would stop at the first
+use learn::result_demo::DemoError;
+use learn::validation::Validated;
+
+fn ok(n: i32) -> Validated<i32, &'static str> {
+    Validated::Valid(n)
+}
+
+fn bad(e: &'static str) -> Validated<i32, &'static str> {
+    Validated::invalid(e)
+}
+
+#[test]
+fn zip_keeps_the_errors_of_both_sides_in_order() {
+    assert_eq!(ok(1).zip(ok(2)).into_result(), Ok((1, 2)));
+    assert_eq!(bad("a").zip(ok(2)).into_result(), Err(vec!["a"]));
+    assert_eq!(ok(1).zip(bad("b")).into_result(), Err(vec!["b"]));
+    assert_eq!(bad("a").zip(bad("b")).into_result(), Err(vec!["a", "b"]));
+}
+
+#[test]
+fn and_then_only_runs_after_a_valid_value() {
+    let mut ran = false;
+    let result = bad("a").and_then(|n| {
+        ran = true;
+        ok(n + 1)
+    });
+    assert_eq!(result.into_result(), Err(vec!["a"]));
+    assert!(!ran);
+    assert_eq!(ok(1).and_then(|n| ok(n + 1)).into_result(), Ok(2));
+    assert_eq!(ok(1).and_then(|_| bad("b")).into_result(), Err(vec!["b"]));
+}
+
+#[test]
+fn collecting_keeps_every_error_or_every_value() {
+    let all: Validated<Vec<i32>, &str> = vec![ok(1), ok(2), ok(3)].into_iter().collect();
+    assert_eq!(all.into_result(), Ok(vec![1, 2, 3]));
+    let some: Validated<Vec<i32>, &str> = vec![ok(1), bad("a"), ok(3), bad("b")].into_iter().collect();
+    assert_eq!(some.into_result(), Err(vec!["a", "b"]));
+    let none: Validated<Vec<i32>, &str> = Vec::new().into_iter().collect();
+    assert_eq!(none.into_result(), Ok(vec![]));
+}
+
+#[test]
+fn map_from_and_check() {
+    assert_eq!(ok(2).map(|n| n * 10).into_result(), Ok(20));
+    assert_eq!(bad("a").map(|n| n * 10).into_result(), Err(vec!["a"]));
+    assert!(Validated::from(Ok::<i32, &str>(1)).is_valid());
+    assert_eq!(Validated::from(Err::<i32, &str>("a")).into_result(), Err(vec!["a"]));
+    assert_eq!(Validated::check(1, Vec::<&str>::new()).into_result(), Ok(1));
+    assert_eq!(Validated::check(1, vec!["a"]).into_result(), Err(vec!["a"]));
+}
+
+#[test]
+fn from_errors_never_builds_an_empty_multiple() {
+    assert!(DemoError::from_errors(Vec::new()).is_none());
+    let one = DemoError::from_errors(vec![DemoError::Message("a".to_string())]);
+    assert!(matches!(one, Some(DemoError::Message(m)) if m == "a"));
+    let two = DemoError::from_errors(vec![DemoError::Message("a".to_string()), DemoError::Message("b".to_string())]);
+    assert!(matches!(two, Some(DemoError::Multiple(errors)) if errors.len() == 2));
+}
+
+#[test]
+fn into_demo_result_groups_several_errors() {
+    let two = Validated::<i32, DemoError>::Invalid(vec![DemoError::Message("a".to_string()), DemoError::Message("b".to_string())]);
+    assert!(matches!(two.into_demo_result(), Err(DemoError::Multiple(errors)) if errors.len() == 2));
+    assert_eq!(Validated::<i32, DemoError>::Valid(7).into_demo_result().unwrap(), 7);
+}